@@ -1,7 +1,7 @@
 use sp_core::{Pair, Public, sr25519};
 use node_template_runtime::{
 	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, ContractsConfig,
-	SudoConfig, SystemConfig, WASM_BINARY, Signature
+	RoyaltyConfig, SudoConfig, SystemConfig, WASM_BINARY, Signature
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
@@ -153,6 +153,7 @@ fn testnet_genesis(
 			// Assign network admin rights.
 			key: root_key,
 		}),
+		pallet_royalty: Some(RoyaltyConfig::default()),
 		pallet_contracts: Some(ContractsConfig {
             current_schedule: pallet_contracts::Schedule {
                     enable_println,
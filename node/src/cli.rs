@@ -35,6 +35,12 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Compute the SRC id (CID) of an audio file and check whether it is registered.
+	SrcCid(crate::src_cid::SrcCidCmd),
+
+	/// Export the royalty statement of a payee for a period as CSV or JSON.
+	RoyaltyStatement(crate::statement::RoyaltyStatementCmd),
+
 	/// The custom benchmark subcommmand benchmarking runtime pallets.
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
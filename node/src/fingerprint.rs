@@ -0,0 +1,383 @@
+//! Acoustic fingerprints of audio files, used to spot re-encodes of registered SRCs.
+//!
+//! The fingerprint follows Haitsma and Kalker, "A Highly Robust Audio Fingerprinting System":
+//! the signal is downmixed and resampled to about 5.5 kHz, cut into overlapping frames and
+//! each frame yields a 32 bit sub-fingerprint from the energy differences of 33 bands between
+//! 300 Hz and 2 kHz. Lossy re-encodes keep most of those bits, so two files are likely the same
+//! recording when their sub-fingerprints agree on most bits at some alignment.
+
+use std::collections::HashMap;
+
+/// Sample rate the audio is resampled to before analysis.
+pub const SAMPLE_RATE: u32 = 5512;
+/// Samples per analysis frame, about 370 ms.
+const FRAME_LENGTH: usize = 2048;
+/// Samples between two frames, about 23 ms.
+const FRAME_HOP: usize = 128;
+const BANDS: usize = 33;
+const MIN_FREQUENCY: f32 = 300.0;
+const MAX_FREQUENCY: f32 = 2000.0;
+
+/// Fingerprints overlapping for fewer frames, about 6 seconds, are never compared.
+pub const MIN_OVERLAP: usize = 256;
+/// Bit error rate under which two fingerprints are reported as the same recording.
+pub const DUPLICATE_BIT_ERROR_RATE: f32 = 0.35;
+/// Number of best-voted alignments checked bit by bit for each candidate.
+const ALIGNMENTS_CHECKED: usize = 8;
+/// `KSDATAFORMAT_SUBTYPE_PCM`, the sub-format of integer PCM in an extensible header.
+const SUBTYPE_PCM: [u8; 16] = [
+	0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xaa, 0x00, 0x38, 0x9b, 0x71,
+];
+
+/// Sequence of 32 bit sub-fingerprints, one per frame.
+pub type Fingerprint = Vec<u32>;
+
+/// Reasons an audio file cannot be fingerprinted.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+	/// The file is not a RIFF/WAVE file.
+	NotWave,
+	/// The WAVE file is not 16 or 24 bit integer PCM, or is sampled below `SAMPLE_RATE`.
+	UnsupportedEncoding,
+	/// The WAVE file ends in the middle of a chunk.
+	Truncated,
+	/// The audio is too short to be fingerprinted.
+	TooShort,
+}
+
+impl std::fmt::Display for Error {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Error::NotWave => write!(f, "not a RIFF/WAVE file"),
+			Error::UnsupportedEncoding => write!(f, "only 16 and 24 bit PCM WAVE files of at least 5.5 kHz are supported"),
+			Error::Truncated => write!(f, "truncated WAVE file"),
+			Error::TooShort => write!(f, "audio too short to fingerprint"),
+		}
+	}
+}
+
+/// Fingerprints a PCM WAVE file.
+pub fn fingerprint_wav(wav: &[u8]) -> Result<Fingerprint, Error> {
+	let (samples, sample_rate) = decode_wav(wav)?;
+	fingerprint(&samples, sample_rate)
+}
+
+/// Fingerprints mono samples in `[-1, 1]` recorded at `sample_rate`.
+pub fn fingerprint(samples: &[f32], sample_rate: u32) -> Result<Fingerprint, Error> {
+	if sample_rate < SAMPLE_RATE {
+		return Err(Error::UnsupportedEncoding);
+	}
+	let samples = resample(samples, sample_rate);
+	if samples.len() < FRAME_LENGTH + FRAME_HOP {
+		return Err(Error::TooShort);
+	}
+
+	let window: Vec<f32> = (0..FRAME_LENGTH)
+		.map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / FRAME_LENGTH as f32).cos())
+		.collect();
+	let band_edges = band_edges();
+	let mut previous: Option<[f32; BANDS]> = None;
+	let mut fingerprint = Vec::with_capacity((samples.len() - FRAME_LENGTH) / FRAME_HOP + 1);
+	let mut re = vec![0.0; FRAME_LENGTH];
+	let mut im = vec![0.0; FRAME_LENGTH];
+
+	for start in (0..=samples.len() - FRAME_LENGTH).step_by(FRAME_HOP) {
+		for i in 0..FRAME_LENGTH {
+			re[i] = samples[start + i] * window[i];
+			im[i] = 0.0;
+		}
+		fft(&mut re, &mut im);
+
+		let mut energies = [0.0f32; BANDS];
+		for (band, energy) in energies.iter_mut().enumerate() {
+			*energy = (band_edges[band]..band_edges[band + 1])
+				.map(|bin| re[bin] * re[bin] + im[bin] * im[bin])
+				.sum();
+		}
+		if let Some(previous) = previous {
+			let mut bits = 0u32;
+			for band in 0..BANDS - 1 {
+				let delta = (energies[band] - energies[band + 1]) - (previous[band] - previous[band + 1]);
+				if delta > 0.0 {
+					bits |= 1 << band;
+				}
+			}
+			fingerprint.push(bits);
+		}
+		previous = Some(energies);
+	}
+	Ok(fingerprint)
+}
+
+/// Lowest bit error rate between `a` and `b` over the alignments where they share
+/// sub-fingerprints, or `None` when no alignment overlaps for `MIN_OVERLAP` frames.
+pub fn bit_error_rate(a: &[u32], b: &[u32]) -> Option<f32> {
+	let mut positions: HashMap<u32, Vec<usize>> = HashMap::new();
+	for (i, bits) in a.iter().enumerate() {
+		// Digital silence yields all-zero sub-fingerprints, which would align anything.
+		if *bits != 0 {
+			positions.entry(*bits).or_default().push(i);
+		}
+	}
+	let mut votes: HashMap<isize, usize> = HashMap::new();
+	for (j, bits) in b.iter().enumerate() {
+		for i in positions.get(bits).into_iter().flatten() {
+			*votes.entry(j as isize - *i as isize).or_default() += 1;
+		}
+	}
+	let mut offsets: Vec<(isize, usize)> = votes.into_iter().collect();
+	offsets.sort_by(|x, y| y.1.cmp(&x.1).then(x.0.cmp(&y.0)));
+
+	offsets.into_iter()
+		.take(ALIGNMENTS_CHECKED)
+		.filter_map(|(offset, _)| aligned_bit_error_rate(a, b, offset))
+		.min_by(|x, y| x.partial_cmp(y).expect("rates are finite; qed"))
+}
+
+/// Bit error rate of `a` against `b` shifted by `offset` frames.
+fn aligned_bit_error_rate(a: &[u32], b: &[u32], offset: isize) -> Option<f32> {
+	let (a, b) = if offset >= 0 {
+		(a, b.get(offset as usize..)?)
+	} else {
+		(a.get((-offset) as usize..)?, b)
+	};
+	let overlap = a.len().min(b.len());
+	if overlap < MIN_OVERLAP {
+		return None;
+	}
+	let errors: u32 = a.iter().zip(b).map(|(x, y)| (x ^ y).count_ones()).sum();
+	Some(errors as f32 / (overlap * 32) as f32)
+}
+
+/// Downmixed samples and sample rate of a 16 or 24 bit PCM WAVE file.
+fn decode_wav(wav: &[u8]) -> Result<(Vec<f32>, u32), Error> {
+	if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+		return Err(Error::NotWave);
+	}
+	let mut format = None;
+	let mut chunks = &wav[12..];
+	while chunks.len() >= 8 {
+		let id = &chunks[0..4];
+		let size = u32::from_le_bytes([chunks[4], chunks[5], chunks[6], chunks[7]]) as usize;
+		let body = chunks.get(8..8 + size).ok_or(Error::Truncated)?;
+		match id {
+			b"fmt " => {
+				if body.len() < 16 {
+					return Err(Error::Truncated);
+				}
+				let tag = u16::from_le_bytes([body[0], body[1]]);
+				let channels = u16::from_le_bytes([body[2], body[3]]) as usize;
+				let sample_rate = u32::from_le_bytes([body[4], body[5], body[6], body[7]]);
+				let bits = u16::from_le_bytes([body[14], body[15]]);
+				// 1 is integer PCM, 0xFFFE the extensible header used for more than two channels.
+				if (tag != 1 && tag != 0xFFFE) || channels == 0 || sample_rate == 0 || (bits != 16 && bits != 24) {
+					return Err(Error::UnsupportedEncoding);
+				}
+				// The extensible header names the actual encoding in its sub-format GUID.
+				if tag == 0xFFFE {
+					let sub_format = body.get(24..40).ok_or(Error::Truncated)?;
+					if sub_format != SUBTYPE_PCM {
+						return Err(Error::UnsupportedEncoding);
+					}
+				}
+				format = Some((channels, sample_rate, bits as usize / 8));
+			}
+			b"data" => {
+				let (channels, sample_rate, width) = format.ok_or(Error::NotWave)?;
+				let samples = body.chunks_exact(width * channels)
+					.map(|frame| {
+						frame.chunks_exact(width).map(|sample| pcm_sample(sample)).sum::<f32>() / channels as f32
+					})
+					.collect();
+				return Ok((samples, sample_rate));
+			}
+			_ => {}
+		}
+		// Chunks are padded to an even size.
+		chunks = chunks.get(8 + size + size % 2..).unwrap_or(&[]);
+	}
+	Err(Error::NotWave)
+}
+
+fn pcm_sample(sample: &[u8]) -> f32 {
+	match sample.len() {
+		2 => i16::from_le_bytes([sample[0], sample[1]]) as f32 / 32_768.0,
+		_ => (i32::from_le_bytes([0, sample[0], sample[1], sample[2]]) >> 8) as f32 / 8_388_608.0,
+	}
+}
+
+/// Resamples to `SAMPLE_RATE` by averaging the input samples falling in each output period,
+/// which doubles as the anti-aliasing filter.
+fn resample(samples: &[f32], sample_rate: u32) -> Vec<f32> {
+	let ratio = sample_rate as f64 / SAMPLE_RATE as f64;
+	let length = (samples.len() as f64 / ratio) as usize;
+	(0..length)
+		.map(|k| {
+			let start = (k as f64 * ratio) as usize;
+			let end = (((k + 1) as f64 * ratio) as usize).min(samples.len()).max(start + 1);
+			samples[start..end].iter().sum::<f32>() / (end - start) as f32
+		})
+		.collect()
+}
+
+/// FFT bins delimiting the logarithmically spaced bands.
+fn band_edges() -> [usize; BANDS + 1] {
+	let mut edges = [0; BANDS + 1];
+	let bin_width = SAMPLE_RATE as f32 / FRAME_LENGTH as f32;
+	for (i, edge) in edges.iter_mut().enumerate() {
+		let frequency = MIN_FREQUENCY * (MAX_FREQUENCY / MIN_FREQUENCY).powf(i as f32 / BANDS as f32);
+		*edge = (frequency / bin_width).round() as usize;
+	}
+	edges
+}
+
+/// In-place iterative radix-2 FFT. The length must be a power of two.
+fn fft(re: &mut [f32], im: &mut [f32]) {
+	let n = re.len();
+	let mut j = 0;
+	for i in 1..n {
+		let mut bit = n >> 1;
+		while j & bit != 0 {
+			j ^= bit;
+			bit >>= 1;
+		}
+		j |= bit;
+		if i < j {
+			re.swap(i, j);
+			im.swap(i, j);
+		}
+	}
+	let mut length = 2;
+	while length <= n {
+		let angle = -2.0 * std::f32::consts::PI / length as f32;
+		for start in (0..n).step_by(length) {
+			for k in 0..length / 2 {
+				let (sin, cos) = (angle * k as f32).sin_cos();
+				let (a, b) = (start + k, start + k + length / 2);
+				let t_re = re[b] * cos - im[b] * sin;
+				let t_im = re[b] * sin + im[b] * cos;
+				re[b] = re[a] - t_re;
+				im[b] = im[a] - t_im;
+				re[a] += t_re;
+				im[a] += t_im;
+			}
+		}
+		length <<= 1;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// Deterministic noise in [-1, 1].
+	fn noise(seed: u32, length: usize) -> Vec<f32> {
+		let mut state = seed;
+		(0..length)
+			.map(|_| {
+				state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+				(state >> 8) as f32 / (1 << 23) as f32 - 1.0
+			})
+			.collect()
+	}
+
+	// Ten seconds of a melody of sliding tones at 44.1 kHz.
+	fn recording(seed: u32) -> Vec<f32> {
+		let rate = 44_100.0;
+		let notes = noise(seed, 40);
+		(0..441_000)
+			.map(|i| {
+				let note = notes[i / 11_025];
+				let frequency = 440.0 * 2f32.powf(note);
+				let t = i as f32 / rate;
+				0.4 * (2.0 * std::f32::consts::PI * frequency * t).sin()
+					+ 0.2 * (2.0 * std::f32::consts::PI * frequency * 1.5 * t).sin()
+			})
+			.collect()
+	}
+
+	#[test]
+	fn fft_finds_tone() {
+		let mut re: Vec<f32> = (0..64).map(|i| (2.0 * std::f32::consts::PI * 5.0 * i as f32 / 64.0).cos()).collect();
+		let mut im = vec![0.0; 64];
+		fft(&mut re, &mut im);
+		assert!((re[5] - 32.0).abs() < 1e-3);
+		assert!(re[6].abs() < 1e-3 && im[6].abs() < 1e-3);
+	}
+
+	#[test]
+	fn re_encodes_match_and_other_recordings_do_not() {
+		let original = recording(1);
+		let reference = fingerprint(&original, 44_100).unwrap();
+
+		// Quieter, noisier and starting 10 ms later, as after a lossy re-encode.
+		let hiss = noise(7, original.len());
+		let re_encode: Vec<f32> = original[441..].iter().zip(&hiss).map(|(s, n)| 0.7 * s + 0.01 * n).collect();
+		let copy = fingerprint(&re_encode, 44_100).unwrap();
+		assert!(bit_error_rate(&reference, &copy).unwrap() < DUPLICATE_BIT_ERROR_RATE);
+
+		let other = fingerprint(&recording(2), 44_100).unwrap();
+		assert!(bit_error_rate(&reference, &other).map_or(true, |rate| rate > DUPLICATE_BIT_ERROR_RATE));
+	}
+
+	#[test]
+	fn decodes_pcm_wave() {
+		let mut wav = Vec::new();
+		let data: Vec<u8> = [1000i16, -1000, 2000, 0].iter().flat_map(|s| s.to_le_bytes().to_vec()).collect();
+		wav.extend_from_slice(b"RIFF");
+		wav.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+		wav.extend_from_slice(b"WAVEfmt ");
+		wav.extend_from_slice(&16u32.to_le_bytes());
+		for field in &[1u16, 2] {
+			wav.extend_from_slice(&field.to_le_bytes());
+		}
+		wav.extend_from_slice(&44_100u32.to_le_bytes());
+		wav.extend_from_slice(&(44_100u32 * 4).to_le_bytes());
+		for field in &[4u16, 16] {
+			wav.extend_from_slice(&field.to_le_bytes());
+		}
+		wav.extend_from_slice(b"data");
+		wav.extend_from_slice(&(data.len() as u32).to_le_bytes());
+		wav.extend_from_slice(&data);
+
+		let (samples, rate) = decode_wav(&wav).unwrap();
+		assert_eq!(rate, 44_100);
+		assert_eq!(samples, vec![0.0, 1000.0 / 32_768.0]);
+		assert_eq!(decode_wav(b"RIFF\0\0\0\0AVI "), Err(Error::NotWave));
+	}
+
+	#[test]
+	fn extensible_wave_must_be_pcm() {
+		let extensible = |sub_format: [u8; 16]| {
+			let mut fmt = Vec::new();
+			for field in &[0xFFFEu16, 2] {
+				fmt.extend_from_slice(&field.to_le_bytes());
+			}
+			fmt.extend_from_slice(&44_100u32.to_le_bytes());
+			fmt.extend_from_slice(&(44_100u32 * 4).to_le_bytes());
+			for field in &[4u16, 16, 22, 16] {
+				fmt.extend_from_slice(&field.to_le_bytes());
+			}
+			fmt.extend_from_slice(&3u32.to_le_bytes());
+			fmt.extend_from_slice(&sub_format);
+
+			let data: Vec<u8> = [1000i16, -1000].iter().flat_map(|s| s.to_le_bytes().to_vec()).collect();
+			let mut wav = Vec::new();
+			wav.extend_from_slice(b"RIFF");
+			wav.extend_from_slice(&(20 + fmt.len() as u32 + data.len() as u32).to_le_bytes());
+			wav.extend_from_slice(b"WAVEfmt ");
+			wav.extend_from_slice(&(fmt.len() as u32).to_le_bytes());
+			wav.extend_from_slice(&fmt);
+			wav.extend_from_slice(b"data");
+			wav.extend_from_slice(&(data.len() as u32).to_le_bytes());
+			wav.extend_from_slice(&data);
+			wav
+		};
+
+		assert_eq!(decode_wav(&extensible(SUBTYPE_PCM)), Ok((vec![0.0], 44_100)));
+		// KSDATAFORMAT_SUBTYPE_IEEE_FLOAT carries floats, not integers, despite the 16 bit size.
+		let mut float = SUBTYPE_PCM;
+		float[0] = 0x03;
+		assert_eq!(decode_wav(&extensible(float)), Err(Error::UnsupportedEncoding));
+	}
+}
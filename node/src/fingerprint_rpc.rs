@@ -0,0 +1,165 @@
+//! RPC methods flagging likely duplicates of registered SRCs before `register_music` is submitted.
+//!
+//! Fingerprints are kept in the node's offchain database, in the persistent storage offchain
+//! workers also read from. They are never part of the chain state: each node indexes the
+//! audio files it has access to.
+//!
+//! The index is maintained by the node operator through `rmp_indexFingerprint`. The chain only
+//! records the CID of an SRC, not its audio, so registering music indexes nothing and duplicates
+//! are only found among the SRCs the operator indexed.
+
+use std::{fs, sync::{Arc, Mutex}};
+
+use codec::{Decode, Encode};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use node_template_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Moment};
+use pallet_rmp::SrcId;
+use pallet_rmp_runtime_api::CatalogApi;
+use sc_rpc_api::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{offchain::{OffchainStorage, STORAGE_PREFIX}, Bytes};
+use sp_runtime::generic::BlockId;
+
+use crate::fingerprint::{self, Fingerprint};
+
+/// Offchain storage key of the list of indexed SRCs.
+const INDEX_KEY: &[u8] = b"rmp::fingerprints";
+/// Offchain storage key prefix of the fingerprint of an SRC.
+const FINGERPRINT_KEY: &[u8] = b"rmp::fingerprint::";
+
+const RUNTIME_ERROR: i64 = 1;
+const AUDIO_ERROR: i64 = 2;
+const NOT_REGISTERED: i64 = 3;
+
+/// Indexed SRC whose fingerprint is close to the one of a queried file.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Duplicate {
+	/// The registered SRC.
+	pub src_id: Bytes,
+	/// Share of differing fingerprint bits at the best alignment, from 0 for identical audio.
+	pub bit_error_rate: f32,
+}
+
+/// Audio fingerprint RPC methods. They read files from the node's file system, so they are
+/// only served to local or explicitly trusted clients.
+#[rpc(server)]
+pub trait FingerprintApi {
+	/// Fingerprint a local PCM WAVE file and index it under a registered SRC. Registering
+	/// music does not index it; the operator calls this for every SRC to check against.
+	#[rpc(name = "rmp_indexFingerprint")]
+	fn index_fingerprint(&self, src_id: Bytes, path: String) -> Result<()>;
+
+	/// Drop the fingerprint indexed under an SRC. Returns whether one was indexed.
+	#[rpc(name = "rmp_removeFingerprint")]
+	fn remove_fingerprint(&self, src_id: Bytes) -> Result<bool>;
+
+	/// Indexed SRCs that are likely the same recording as a local PCM WAVE file, best match first.
+	/// SRCs this node never indexed are not found.
+	#[rpc(name = "rmp_findDuplicates")]
+	fn find_duplicates(&self, path: String) -> Result<Vec<Duplicate>>;
+}
+
+/// Implements the [`FingerprintApi`] on top of the offchain database.
+pub struct FingerprintIndex<C, S> {
+	client: Arc<C>,
+	// Serializes updates of the list of indexed SRCs.
+	storage: Mutex<S>,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<C, S> FingerprintIndex<C, S> {
+	/// Create a new instance backed by the given offchain storage.
+	pub fn new(client: Arc<C>, storage: S, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, storage: Mutex::new(storage), deny_unsafe }
+	}
+}
+
+impl<C, S> FingerprintApi for FingerprintIndex<C, S> where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: CatalogApi<Block, AccountId, Balance, BlockNumber, Hash, Moment>,
+	S: OffchainStorage + 'static,
+{
+	fn index_fingerprint(&self, src_id: Bytes, path: String) -> Result<()> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let src_id: SrcId = src_id.0;
+		let at = BlockId::Hash(self.client.info().best_hash);
+		let owner = self.client.runtime_api()
+			.owner_of(&at, src_id.clone())
+			.map_err(|e| error(RUNTIME_ERROR, "Unable to query the SRC owner.", e))?;
+		if owner.is_none() {
+			return Err(error(NOT_REGISTERED, "SRC is not registered.", ""));
+		}
+		let fingerprint = fingerprint_file(&path)?;
+
+		let mut storage = self.storage.lock().expect("fingerprint index lock poisoned");
+		storage.set(STORAGE_PREFIX, &fingerprint_key(&src_id), &fingerprint.encode());
+		let mut indexed = indexed_srcs(&*storage);
+		if !indexed.contains(&src_id) {
+			indexed.push(src_id);
+			storage.set(STORAGE_PREFIX, INDEX_KEY, &indexed.encode());
+		}
+		Ok(())
+	}
+
+	fn remove_fingerprint(&self, src_id: Bytes) -> Result<bool> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let mut storage = self.storage.lock().expect("fingerprint index lock poisoned");
+		let mut indexed = indexed_srcs(&*storage);
+		let before = indexed.len();
+		indexed.retain(|id| id != &src_id.0);
+		if indexed.len() == before {
+			return Ok(false);
+		}
+		storage.remove(STORAGE_PREFIX, &fingerprint_key(&src_id.0));
+		storage.set(STORAGE_PREFIX, INDEX_KEY, &indexed.encode());
+		Ok(true)
+	}
+
+	fn find_duplicates(&self, path: String) -> Result<Vec<Duplicate>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let query = fingerprint_file(&path)?;
+		let storage = self.storage.lock().expect("fingerprint index lock poisoned").clone();
+		let mut duplicates: Vec<Duplicate> = indexed_srcs(&storage)
+			.into_iter()
+			.filter_map(|src_id| {
+				let stored = storage.get(STORAGE_PREFIX, &fingerprint_key(&src_id))?;
+				let stored = Fingerprint::decode(&mut &stored[..]).ok()?;
+				fingerprint::bit_error_rate(&stored, &query)
+					.filter(|rate| *rate < fingerprint::DUPLICATE_BIT_ERROR_RATE)
+					.map(|bit_error_rate| Duplicate { src_id: src_id.into(), bit_error_rate })
+			})
+			.collect();
+		duplicates.sort_by(|a, b| a.bit_error_rate.partial_cmp(&b.bit_error_rate).expect("rates are finite; qed"));
+		Ok(duplicates)
+	}
+}
+
+fn fingerprint_file(path: &str) -> Result<Fingerprint> {
+	let wav = fs::read(path).map_err(|e| error(AUDIO_ERROR, "Unable to read the audio file.", e))?;
+	fingerprint::fingerprint_wav(&wav).map_err(|e| error(AUDIO_ERROR, "Unable to fingerprint the audio file.", e))
+}
+
+fn indexed_srcs<S: OffchainStorage>(storage: &S) -> Vec<SrcId> {
+	storage.get(STORAGE_PREFIX, INDEX_KEY)
+		.and_then(|encoded| Vec::<SrcId>::decode(&mut &encoded[..]).ok())
+		.unwrap_or_default()
+}
+
+fn fingerprint_key(src_id: &[u8]) -> Vec<u8> {
+	[FINGERPRINT_KEY, src_id].concat()
+}
+
+fn error(code: i64, message: &str, cause: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(code),
+		message: message.into(),
+		data: Some(format!("{:?}", cause).into()),
+	}
+}
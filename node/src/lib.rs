@@ -1,3 +1,7 @@
 pub mod chain_spec;
 pub mod service;
 pub mod rpc;
+pub mod fingerprint;
+pub mod fingerprint_rpc;
+pub mod statement;
+pub mod statement_rpc;
@@ -7,6 +7,11 @@ mod service;
 mod cli;
 mod command;
 mod rpc;
+mod fingerprint;
+mod fingerprint_rpc;
+mod src_cid;
+mod statement;
+mod statement_rpc;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
@@ -7,29 +7,34 @@
 
 use std::sync::Arc;
 
-use node_template_runtime::{opaque::Block, AccountId, Balance, Index, BlockNumber};
+use node_template_runtime::{opaque::Block, AccountId, AssetId, Balance, Index, BlockNumber, Hash, Moment};
 use pallet_contracts_rpc::{Contracts, ContractsApi};
+use crate::fingerprint_rpc::{FingerprintApi, FingerprintIndex};
+use crate::statement_rpc::{StatementApi, Statements};
 
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::{Error as BlockChainError, HeaderMetadata, HeaderBackend};
 use sp_block_builder::BlockBuilder;
+use sp_core::offchain::OffchainStorage;
 pub use sc_rpc_api::DenyUnsafe;
 use sp_transaction_pool::TransactionPool;
 
 
 /// Full client dependencies.
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, P, S> {
 	/// The client instance to use.
 	pub client: Arc<C>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
+	/// Offchain database holding the audio fingerprint index, if the backend has one.
+	pub offchain_db: Option<S>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
 }
 
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P>(
-	deps: FullDeps<C, P>,
+pub fn create_full<C, P, S>(
+	deps: FullDeps<C, P, S>,
 ) -> jsonrpc_core::IoHandler<sc_rpc::Metadata> where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error=BlockChainError> + 'static,
@@ -37,8 +42,11 @@ pub fn create_full<C, P>(
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_rmp_runtime_api::CatalogApi<Block, AccountId, Balance, BlockNumber, Hash, Moment>,
+	C::Api: pallet_royalty_runtime_api::RoyaltyApi<Block, AccountId, Balance, AssetId, Balance, BlockNumber>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
+	S: OffchainStorage + 'static,
 {
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
@@ -47,6 +55,7 @@ pub fn create_full<C, P>(
 	let FullDeps {
 		client,
 		pool,
+		offchain_db,
 		deny_unsafe,
 	} = deps;
 
@@ -68,5 +77,17 @@ pub fn create_full<C, P>(
         ContractsApi::to_delegate(Contracts::new(client.clone()))
     );
 
+	// Royalty statement RPC API extension
+	io.extend_with(
+		StatementApi::to_delegate(Statements::new(client.clone()))
+	);
+
+	// Audio fingerprint RPC API extension
+	if let Some(offchain_db) = offchain_db {
+		io.extend_with(
+			FingerprintApi::to_delegate(FingerprintIndex::new(client.clone(), offchain_db, deny_unsafe))
+		);
+	}
+
 	io
 }
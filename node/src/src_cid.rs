@@ -0,0 +1,94 @@
+//! `src-cid` subcommand: computes the SRC id of a local audio file and looks it up on chain.
+
+use std::{path::PathBuf, sync::Arc};
+
+use node_template_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Moment};
+use pallet_rmp::cid::{cid_v1, CODEC_RAW, SHA2_256};
+use pallet_rmp_runtime_api::CatalogApi;
+use sc_cli::{CliConfiguration, ImportParams, SharedParams};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::hashing::sha2_256;
+use sp_runtime::generic::BlockId;
+use structopt::StructOpt;
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+// Default chunk size of `ipfs add`; larger files become a DAG of chunks.
+const IPFS_CHUNK_SIZE: usize = 256 * 1024;
+
+/// The `src-cid` command.
+///
+/// The CID is the raw-block CIDv1 of the whole file (`raw` codec, sha2-256 of the file
+/// bytes). It matches what `ipfs add --cid-version 1 --raw-leaves` reports only for files
+/// that fit in a single chunk; larger files are added as a `dag-pb` UnixFS DAG whose root
+/// CID differs.
+#[derive(Debug, StructOpt)]
+pub struct SrcCidCmd {
+	/// Audio file to identify.
+	#[structopt(parse(from_os_str))]
+	pub file: PathBuf,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub import_params: ImportParams,
+}
+
+impl SrcCidCmd {
+	/// Print the CID of the file and whether it is registered at the best block.
+	pub async fn run<C>(&self, client: Arc<C>) -> sc_cli::Result<()> where
+		C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+		C::Api: CatalogApi<Block, AccountId, Balance, BlockNumber, Hash, Moment>,
+	{
+		let audio = std::fs::read(&self.file)?;
+		let src_id = cid_v1(CODEC_RAW, SHA2_256, &sha2_256(&audio));
+		println!("Raw-block CID: {}", multibase_base32(&src_id));
+		println!("src_id:        0x{}", sp_core::hexdisplay::HexDisplay::from(&src_id));
+		if audio.len() > IPFS_CHUNK_SIZE {
+			println!("Note: `ipfs add` chunks files over 256 KiB and reports a different, dag-pb CID");
+		}
+
+		let at = BlockId::Hash(client.info().best_hash);
+		let owner = client.runtime_api()
+			.owner_of(&at, src_id)
+			.map_err(|e| format!("Failed to query the runtime: {:?}", e))?;
+		match owner {
+			Some(owner) => println!("Registered, owned by {}", owner),
+			None => println!("Not registered"),
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for SrcCidCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		Some(&self.import_params)
+	}
+}
+
+/// Multibase `b` encoding: unpadded lowercase RFC 4648 base32, as used by IPFS for CIDv1.
+fn multibase_base32(bytes: &[u8]) -> String {
+	let mut encoded = String::with_capacity(1 + (bytes.len() * 8 + 4) / 5);
+	encoded.push('b');
+	let mut buffer = 0u16;
+	let mut bits = 0;
+	for byte in bytes {
+		buffer = (buffer << 8) | u16::from(*byte);
+		bits += 8;
+		while bits >= 5 {
+			bits -= 5;
+			encoded.push(BASE32_ALPHABET[usize::from((buffer >> bits) & 0x1f)] as char);
+		}
+	}
+	if bits > 0 {
+		encoded.push(BASE32_ALPHABET[usize::from((buffer << (5 - bits)) & 0x1f)] as char);
+	}
+	encoded
+}
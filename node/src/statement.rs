@@ -0,0 +1,234 @@
+//! Royalty statements drawn up from chain state, served by the `rsp_statement` RPC methods
+//! and exported by the `royalty-statement` subcommand.
+
+use std::{fmt::Write, path::PathBuf, str::FromStr, sync::Arc};
+
+use node_template_runtime::{opaque::Block, AccountId, AssetId, Balance, BlockNumber};
+use pallet_royalty_runtime_api::RoyaltyApi;
+use sc_cli::{CliConfiguration, ImportParams, SharedParams};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{crypto::Ss58Codec, hexdisplay::HexDisplay};
+use sp_runtime::generic::BlockId;
+use structopt::StructOpt;
+
+/// Royalties earned by a payee during a statement period.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RoyaltyStatement {
+	/// SS58 address of the payee.
+	pub payee: String,
+	/// Index of the statement period.
+	pub period: BlockNumber,
+	/// First block of the period.
+	pub first_block: BlockNumber,
+	/// Last block of the period.
+	pub last_block: BlockNumber,
+	/// One line per SRC and currency, native royalties first.
+	pub lines: Vec<StatementLine>,
+}
+
+/// Royalties earned for an SRC in one currency.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementLine {
+	/// Hex encoded SRC id.
+	pub src_id: String,
+	/// Asset the royalties were paid in, `None` for the native currency.
+	pub asset_id: Option<AssetId>,
+	/// Amount earned before withholding, as a decimal string so that 128-bit balances
+	/// survive JSON parsers; so are the other amounts.
+	pub gross: String,
+	/// Amount withheld for the tax account.
+	pub withheld: String,
+	/// Amount paid to the payee.
+	pub net: String,
+}
+
+impl RoyaltyStatement {
+	/// Draw up the statement of `payee` for `period`, or the current period, at block `at`.
+	pub fn fetch<C>(
+		client: &C,
+		at: BlockId<Block>,
+		payee: &AccountId,
+		period: Option<BlockNumber>,
+	) -> Result<Self, String> where
+		C: ProvideRuntimeApi<Block>,
+		C::Api: RoyaltyApi<Block, AccountId, Balance, AssetId, Balance, BlockNumber>,
+	{
+		let api = client.runtime_api();
+		let period = match period {
+			Some(period) => period,
+			None => api.current_period(&at).map_err(|e| format!("Failed to query the runtime: {:?}", e))?,
+		};
+		let statement = api.statement(&at, payee.clone(), period)
+			.map_err(|e| format!("Failed to query the runtime: {:?}", e))?;
+
+		let native = statement.earnings().iter().map(|(src_id, earning)| StatementLine {
+			src_id: format!("0x{}", HexDisplay::from(src_id)),
+			asset_id: None,
+			gross: earning.gross().to_string(),
+			withheld: earning.withheld().to_string(),
+			net: earning.net().to_string(),
+		});
+		let assets = statement.asset_earnings().iter().map(|(src_id, asset_id, earning)| StatementLine {
+			src_id: format!("0x{}", HexDisplay::from(src_id)),
+			asset_id: Some(*asset_id),
+			gross: earning.gross().to_string(),
+			withheld: earning.withheld().to_string(),
+			net: earning.net().to_string(),
+		});
+		Ok(Self {
+			payee: payee.to_ss58check(),
+			period,
+			first_block: *statement.first_block(),
+			last_block: *statement.last_block(),
+			lines: native.chain(assets).collect(),
+		})
+	}
+
+	/// CSV rendering with a header row and one row per line. The asset column is empty for
+	/// royalties in the native currency.
+	pub fn to_csv(&self) -> String {
+		let mut csv = String::from("payee,period,first_block,last_block,src_id,asset_id,gross,withheld,net\n");
+		for line in &self.lines {
+			let asset_id = line.asset_id.map(|asset_id| asset_id.to_string()).unwrap_or_default();
+			let _ = writeln!(
+				csv,
+				"{},{},{},{},{},{},{},{},{}",
+				self.payee,
+				self.period,
+				self.first_block,
+				self.last_block,
+				line.src_id,
+				asset_id,
+				line.gross,
+				line.withheld,
+				line.net,
+			);
+		}
+		csv
+	}
+}
+
+/// Output format of the `royalty-statement` subcommand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatementFormat {
+	/// Comma-separated values, one row per statement line.
+	Csv,
+	/// The JSON document returned by the `rsp_statement` RPC method.
+	Json,
+}
+
+impl FromStr for StatementFormat {
+	type Err = String;
+
+	fn from_str(format: &str) -> Result<Self, Self::Err> {
+		match format {
+			"csv" => Ok(StatementFormat::Csv),
+			"json" => Ok(StatementFormat::Json),
+			_ => Err(format!("Unknown statement format `{}`, expected `csv` or `json`", format)),
+		}
+	}
+}
+
+/// The `royalty-statement` command.
+#[derive(Debug, StructOpt)]
+pub struct RoyaltyStatementCmd {
+	/// SS58 address of the payee.
+	#[structopt(long, parse(try_from_str = parse_account))]
+	pub payee: AccountId,
+
+	/// Statement period to export; defaults to the current one.
+	#[structopt(long)]
+	pub period: Option<BlockNumber>,
+
+	/// Output format, `csv` or `json`.
+	#[structopt(long, default_value = "csv")]
+	pub format: StatementFormat,
+
+	/// File to write the statement to instead of the standard output.
+	#[structopt(long, parse(from_os_str))]
+	pub output: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub import_params: ImportParams,
+}
+
+impl RoyaltyStatementCmd {
+	/// Export the statement of the payee at the best block.
+	pub async fn run<C>(&self, client: Arc<C>) -> sc_cli::Result<()> where
+		C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+		C::Api: RoyaltyApi<Block, AccountId, Balance, AssetId, Balance, BlockNumber>,
+	{
+		let at = BlockId::Hash(client.info().best_hash);
+		let statement = RoyaltyStatement::fetch(&*client, at, &self.payee, self.period)?;
+		let rendered = match self.format {
+			StatementFormat::Csv => statement.to_csv(),
+			StatementFormat::Json => serde_json::to_string_pretty(&statement)
+				.map_err(|e| format!("Failed to encode the statement: {}", e))? + "\n",
+		};
+		match &self.output {
+			Some(path) => std::fs::write(path, rendered)?,
+			None => print!("{}", rendered),
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for RoyaltyStatementCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		Some(&self.import_params)
+	}
+}
+
+fn parse_account(address: &str) -> Result<AccountId, String> {
+	AccountId::from_ss58check(address).map_err(|e| format!("Invalid SS58 address `{}`: {:?}", address, e))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn csv_has_one_row_per_line() {
+		let statement = RoyaltyStatement {
+			payee: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".into(),
+			period: 3,
+			first_block: 1_296_000,
+			last_block: 1_727_999,
+			lines: vec![
+				StatementLine {
+					src_id: "0x01551220ab".into(),
+					asset_id: None,
+					gross: "70".into(),
+					withheld: "21".into(),
+					net: "49".into(),
+				},
+				StatementLine {
+					src_id: "0x01551220ab".into(),
+					asset_id: Some(7),
+					gross: "12".into(),
+					withheld: "0".into(),
+					net: "12".into(),
+				},
+			],
+		};
+		assert_eq!(
+			statement.to_csv(),
+			"payee,period,first_block,last_block,src_id,asset_id,gross,withheld,net\n\
+			5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY,3,1296000,1727999,0x01551220ab,,70,21,49\n\
+			5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY,3,1296000,1727999,0x01551220ab,7,12,0,12\n"
+		);
+	}
+}
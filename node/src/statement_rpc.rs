@@ -0,0 +1,57 @@
+//! RPC methods serving the royalty statements of a payee.
+
+use std::sync::Arc;
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use node_template_runtime::{opaque::Block, AccountId, AssetId, Balance, BlockNumber, Hash};
+use pallet_royalty_runtime_api::RoyaltyApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::generic::BlockId;
+
+use crate::statement::RoyaltyStatement;
+
+const RUNTIME_ERROR: i64 = 1;
+
+/// Royalty statement RPC methods.
+#[rpc(server)]
+pub trait StatementApi {
+	/// Statement of `payee` for `period`, or the current period, at block `at`, or the best block.
+	#[rpc(name = "rsp_statement")]
+	fn statement(&self, payee: AccountId, period: Option<BlockNumber>, at: Option<Hash>) -> Result<RoyaltyStatement>;
+
+	/// Same statement as `rsp_statement`, rendered as CSV.
+	#[rpc(name = "rsp_statementCsv")]
+	fn statement_csv(&self, payee: AccountId, period: Option<BlockNumber>, at: Option<Hash>) -> Result<String>;
+}
+
+/// Implements the [`StatementApi`] on top of the runtime's `RoyaltyApi`.
+pub struct Statements<C> {
+	client: Arc<C>,
+}
+
+impl<C> Statements<C> {
+	/// Create a new instance querying the given client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C> StatementApi for Statements<C> where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: RoyaltyApi<Block, AccountId, Balance, AssetId, Balance, BlockNumber>,
+{
+	fn statement(&self, payee: AccountId, period: Option<BlockNumber>, at: Option<Hash>) -> Result<RoyaltyStatement> {
+		let at = BlockId::Hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		RoyaltyStatement::fetch(&*self.client, at, &payee, period).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(RUNTIME_ERROR),
+			message: "Unable to draw up the statement.".into(),
+			data: Some(e.into()),
+		})
+	}
+
+	fn statement_csv(&self, payee: AccountId, period: Option<BlockNumber>, at: Option<Hash>) -> Result<String> {
+		self.statement(payee, period, at).map(|statement| statement.to_csv())
+	}
+}
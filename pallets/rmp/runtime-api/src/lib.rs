@@ -0,0 +1,128 @@
+//! Runtime API definition for querying the Rights Management catalog.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+use pallet_rmp::{
+	Album, AlbumId, Artist, ArtistId, ArtistName, Comp, CompositionId, Credit, Derivation, Genre,
+	GenreId, Grant, Gtin, IpiNameNumber, Isni, License, LicenseId, LicenseTemplate, LicenseType,
+	Localizations, MusicData, Party, PartyId, Sample, SrcId, Takedown, Territory, TerritoryRights,
+	Work,
+};
+
+sp_api::decl_runtime_apis! {
+	/// Read access to registered SRCs and albums.
+	pub trait CatalogApi<AccountId, Balance, BlockNumber, Hash, Moment> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
+		Moment: Codec,
+	{
+		/// Music data registered under `src_id`.
+		fn music_by_src_id(src_id: SrcId) -> Option<MusicData<AccountId, Moment>>;
+
+		/// Current owner of an SRC, which differs from the registered owner while rights are granted.
+		fn owner_of(src_id: SrcId) -> Option<AccountId>;
+
+		/// Album registered under `album_id`.
+		fn album_by_id(album_id: AlbumId) -> Option<Album<Moment>>;
+
+		/// Album carrying a UPC-A or EAN-13 barcode.
+		fn album_by_gtin(gtin: Gtin) -> Option<AlbumId>;
+
+		/// Localized titles and artist names of an SRC.
+		fn src_localizations(src_id: SrcId) -> Option<Localizations>;
+
+		/// Localized titles and artist names of an album.
+		fn album_localizations(album_id: AlbumId) -> Option<Localizations>;
+
+		/// SRCs on an album, in track order.
+		fn album_tracks(album_id: AlbumId) -> Vec<SrcId>;
+
+		/// Territory rights declared for an SRC.
+		fn territory_rights_of(src_id: SrcId) -> Vec<TerritoryRights<Moment>>;
+
+		/// Whether an SRC may be exploited in `territory` at `time`.
+		fn is_available_in(src_id: SrcId, territory: Territory, time: Moment) -> bool;
+
+		/// Entry of the genre taxonomy.
+		fn genre(genre_id: GenreId) -> Option<Genre>;
+
+		/// All SRCs tagged with `genre_id`.
+		fn srcs_by_genre(genre_id: GenreId) -> Vec<SrcId>;
+
+		/// Samples declared by an SRC with their clearance status.
+		fn samples_of(src_id: SrcId) -> Vec<Sample>;
+
+		/// SRCs that declared sampling `src_id`.
+		fn sampled_by(src_id: SrcId) -> Vec<SrcId>;
+
+		/// Whether an SRC contains samples that are undeclared or not cleared.
+		fn has_uncleared_samples(src_id: SrcId) -> bool;
+
+		/// Composition registered under `composition_id`.
+		fn composition_by_id(composition_id: CompositionId) -> Option<Comp<Moment>>;
+
+		/// Works an SRC declared to be derived from.
+		fn derived_from(src_id: SrcId) -> Vec<Derivation>;
+
+		/// All SRCs derived from `work`, directly or transitively.
+		fn all_derivatives(work: Work) -> Vec<SrcId>;
+
+		/// Contributor credits of an SRC.
+		fn credits_of(src_id: SrcId) -> Vec<Credit<AccountId>>;
+
+		/// SRCs crediting `account`.
+		fn credited_srcs(account: AccountId) -> Vec<SrcId>;
+
+		/// Credits of a composition.
+		fn composition_credits_of(composition_id: CompositionId) -> Vec<Credit<AccountId>>;
+
+		/// Party registered under `party_id`.
+		fn party(party_id: PartyId) -> Option<Party<AccountId>>;
+
+		/// Party holding an ISNI.
+		fn party_by_isni(isni: Isni) -> Option<PartyId>;
+
+		/// Party holding an IPI name number.
+		fn party_by_ipi(ipi: IpiNameNumber) -> Option<PartyId>;
+
+		/// Artist registered under `artist_id`, or the artist it was merged into.
+		fn artist(artist_id: ArtistId) -> Option<Artist<AccountId>>;
+
+		/// Canonical artist owning `name` as an alias.
+		fn resolve_alias(name: ArtistName) -> Option<ArtistId>;
+
+		/// Canonical artists credited on an SRC.
+		fn artists_of_src(src_id: SrcId) -> Vec<ArtistId>;
+
+		/// Active rights grants on an SRC, from the original owner to the current one.
+		fn grant_chain(src_id: SrcId) -> Vec<Grant<AccountId, BlockNumber>>;
+
+		/// Licenses offered for an SRC.
+		fn license_templates_of(src_id: SrcId) -> Vec<LicenseTemplate<Balance, BlockNumber>>;
+
+		/// License issued under `license_id`.
+		fn license(license_id: LicenseId) -> Option<License<AccountId, Balance, BlockNumber>>;
+
+		/// Licenses purchased by `licensee`.
+		fn licenses_of(licensee: AccountId) -> Vec<LicenseId>;
+
+		/// Whether a license currently lets `licensee` use an SRC for `license_type` in `territory`.
+		fn verify_license(
+			license_id: LicenseId,
+			licensee: AccountId,
+			src_id: SrcId,
+			license_type: LicenseType,
+			territory: Territory,
+		) -> bool;
+
+		/// Open takedown notice against an SRC.
+		fn takedown_of(src_id: SrcId) -> Option<Takedown<AccountId, Balance, BlockNumber, Hash>>;
+
+		/// Whether an SRC is suspended by a takedown notice.
+		fn is_suspended(src_id: SrcId) -> bool;
+	}
+}
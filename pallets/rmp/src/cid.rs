@@ -0,0 +1,121 @@
+//! Binary CIDv1 content identifiers, used as SRC ids.
+//!
+//! A CIDv1 is the concatenation of unsigned varints `<version><codec><hash function><digest length>`
+//! followed by the digest. Only the binary form is accepted on chain so that each piece of
+//! content maps to a single id whatever multibase it was displayed in.
+
+use sp_std::vec::Vec;
+
+pub const CID_VERSION_1: u64 = 0x01;
+
+pub const CODEC_RAW: u64 = 0x55;
+pub const CODEC_DAG_PB: u64 = 0x70;
+
+pub const SHA2_256: u64 = 0x12;
+pub const SHA2_512: u64 = 0x13;
+pub const BLAKE2B_256: u64 = 0xb220;
+pub const BLAKE2B_512: u64 = 0xb240;
+
+// Longest varint needed for a 64-bit value.
+const VARINT_MAX_LENGTH: usize = 10;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CidError {
+    Empty,
+    Malformed,
+    UnsupportedVersion,
+    UnsupportedCodec,
+    UnsupportedHash,
+    DigestLengthMismatch,
+}
+
+/// Parsed view of a binary CIDv1.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cid<'a> {
+    codec: u64,
+    hash: u64,
+    digest: &'a [u8],
+}
+
+impl<'a> Cid<'a> {
+    /// Parses a binary CIDv1, checking its codec and hash function are supported and
+    /// its digest has the length the hash function produces.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, CidError> {
+        if bytes.is_empty() {
+            return Err(CidError::Empty);
+        }
+        let mut rest = bytes;
+        // CIDv0 are bare sha2-256 multihashes, starting with the sha2-256 code.
+        if read_varint(&mut rest)? != CID_VERSION_1 {
+            return Err(CidError::UnsupportedVersion);
+        }
+        let codec = read_varint(&mut rest)?;
+        if codec != CODEC_RAW && codec != CODEC_DAG_PB {
+            return Err(CidError::UnsupportedCodec);
+        }
+        let hash = read_varint(&mut rest)?;
+        let expected = digest_length(hash).ok_or(CidError::UnsupportedHash)?;
+        if read_varint(&mut rest)? != expected as u64 || rest.len() != expected {
+            return Err(CidError::DigestLengthMismatch);
+        }
+        Ok(Self { codec, hash, digest: rest })
+    }
+
+    pub fn codec(&self) -> u64 {
+        self.codec
+    }
+
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    pub fn digest(&self) -> &'a [u8] {
+        self.digest
+    }
+}
+
+/// Encodes a binary CIDv1 for `digest`, computed with the `hash` multihash function over
+/// content of type `codec`.
+pub fn cid_v1(codec: u64, hash: u64, digest: &[u8]) -> Vec<u8> {
+    let mut cid = Vec::with_capacity(4 * VARINT_MAX_LENGTH + digest.len());
+    write_varint(CID_VERSION_1, &mut cid);
+    write_varint(codec, &mut cid);
+    write_varint(hash, &mut cid);
+    write_varint(digest.len() as u64, &mut cid);
+    cid.extend_from_slice(digest);
+    cid
+}
+
+/// Digest length in bytes of a supported multihash function.
+pub fn digest_length(hash: u64) -> Option<usize> {
+    match hash {
+        SHA2_256 | BLAKE2B_256 => Some(32),
+        SHA2_512 | BLAKE2B_512 => Some(64),
+        _ => None,
+    }
+}
+
+fn read_varint<'a>(bytes: &mut &'a [u8]) -> Result<u64, CidError> {
+    let data: &'a [u8] = *bytes;
+    let mut value = 0u64;
+    for (i, byte) in data.iter().enumerate().take(VARINT_MAX_LENGTH) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            // Multiformats require the minimal encoding, so no trailing zero groups.
+            if i > 0 && *byte == 0 {
+                return Err(CidError::Malformed);
+            }
+            *bytes = &data[i + 1..];
+            return Ok(value);
+        }
+    }
+    Err(CidError::Malformed)
+}
+
+fn write_varint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
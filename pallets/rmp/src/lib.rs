@@ -6,9 +6,15 @@ use codec::{Decode, Encode};
 use core::result::Result;
 use frame_support::{decl_module, decl_storage, decl_event, decl_error, dispatch, ensure, 
  	sp_std::prelude::*};
+use frame_support::sp_runtime::traits::{Hash as HashT, Saturating, Zero};
+use frame_support::traits::{BalanceStatus, Currency, EnsureOrigin, ExistenceRequirement, Get, ReservableCurrency};
+use frame_support::weights::Weight;
 use frame_system::ensure_signed;
+use cid::CidError;
 pub use sp_std::vec::Vec;
 
+pub mod cid;
+
 #[cfg(test)]
 mod mock;
 
@@ -16,7 +22,9 @@ mod mock;
 mod tests;
 
 // General constraints to limit data size
-pub const SRC_ID_MAX_LENGTH: usize = 36;
+// Binary CIDv1 with a 64-byte digest: version, codec and length varints of one byte and
+// a hash function varint of up to three bytes.
+pub const SRC_ID_MAX_LENGTH: usize = 70;
 pub const SONG_ID_MAX_LENGTH: usize = 36;
 pub const SONG_NAME_MAX_LENGTH: usize = 20;
 pub const ARTIST_NAME_MAX_LENGTH: usize = 20;
@@ -24,12 +32,101 @@ pub const COMPOSER_MAX_LENGTH: usize = 20;
 pub const LYRICIST_MAX_LENGTH: usize = 20;
 pub const YOR_MAX_LENGTH: usize = 4;
 pub const SONG_MAX_PROPS: usize = 6;
+pub const ALBUM_ID_MAX_LENGTH: usize = 36;
+pub const ALBUM_MAX_TRACKS: usize = 64;
+pub const COMPOSITION_ID_MAX_LENGTH: usize = 36;
+pub const LANGUAGE_TAG_MAX_LENGTH: usize = 35;
+pub const LOCALIZED_TITLE_MAX_LENGTH: usize = 100;
+pub const LOCALIZED_ARTIST_MAX_LENGTH: usize = 100;
+pub const LOCALIZATION_MAX_ENTRIES: usize = 16;
+pub const TERRITORY_RIGHTS_MAX_ENTRIES: usize = 16;
+pub const TERRITORY_LIST_MAX_LENGTH: usize = 250;
+pub const GENRE_NAME_MAX_LENGTH: usize = 50;
+pub const GENRE_MAX_DEPTH: usize = 8;
+pub const SRC_MAX_GENRES: usize = 2;
+pub const SRC_MAX_SAMPLES: usize = 16;
+pub const SRC_MAX_DERIVATIONS: usize = 8;
+pub const DERIVATIVES_QUERY_MAX_RESULTS: usize = 256;
+pub const SRC_MAX_CREDITS: usize = 64;
+pub const CREDIT_NAME_MAX_LENGTH: usize = 100;
+pub const CREDIT_ROLE_MAX_LENGTH: usize = 32;
+pub const PARTY_NAME_MAX_LENGTH: usize = 100;
+pub const CATALOG_NUMBER_MAX_LENGTH: usize = 32;
+pub const ARTIST_ALIAS_MAX_LENGTH: usize = 100;
+pub const ARTIST_MAX_ALIASES: usize = 32;
+pub const GRANT_MAX_CHAIN_LENGTH: usize = 8;
+pub const GRANT_MAX_EXPIRIES_PER_BLOCK: usize = 32;
+pub const SRC_MAX_LICENSE_TEMPLATES: usize = 16;
+pub const TAKEDOWN_MAX_DEADLINES_PER_BLOCK: usize = 32;
+// Basis points making up 100% of a royalty.
+pub const MAX_BASIS_POINTS: u32 = 10_000;
+
+/// Lets other pallets keep state an SRC owner wrote in step with grants of the rights.
+pub trait OnRightsChange<AccountId> {
+	/// The rights on `src_id` were granted from `grantor` to `grantee`.
+	fn on_granted(src_id: &[u8], grantor: &AccountId, grantee: &AccountId) -> Weight;
+
+	/// An expired grant handed the rights on `src_id` back from `grantee` to `grantor`;
+	/// whatever the grantee set up during the grant should be undone.
+	fn on_reverted(src_id: &[u8], grantee: &AccountId, grantor: &AccountId) -> Weight;
+
+	/// Upper bound of the weight returned by `on_granted`, charged up front by `grant_rights`.
+	fn max_weight() -> Weight;
+}
+
+impl<AccountId> OnRightsChange<AccountId> for () {
+	fn on_granted(_: &[u8], _: &AccountId, _: &AccountId) -> Weight { 0 }
+	fn on_reverted(_: &[u8], _: &AccountId, _: &AccountId) -> Weight { 0 }
+	fn max_weight() -> Weight { 0 }
+}
+
+/// Lets other pallets act on the outcome of a takedown notice.
+pub trait OnTakedownSettled<AccountId> {
+	/// The notice `claimant` filed against `src_id` was closed; an `upheld` notice leaves the
+	/// SRC suspended, any other reinstates it.
+	fn on_settled(src_id: &[u8], claimant: &AccountId, upheld: bool) -> Weight;
+
+	/// Upper bound of the weight returned by `on_settled`, charged up front by `resolve_takedown`.
+	fn max_weight() -> Weight;
+}
+
+impl<AccountId> OnTakedownSettled<AccountId> for () {
+	fn on_settled(_: &[u8], _: &AccountId, _: bool) -> Weight { 0 }
+	fn max_weight() -> Weight { 0 }
+}
 
 pub trait Config: frame_system::Config + timestamp::Config {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
+
+	/// Origin allowed to manage the genre taxonomy.
+	type GenreOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Currency in which licenses are paid and takedown bonds are reserved.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// Origin allowed to resolve disputed takedown notices.
+	type TakedownOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Bond reserved from a claimant filing a notice and from an owner filing a counter-notice.
+	type TakedownBond: Get<BalanceOf<Self>>;
+
+	/// Number of blocks after which an unanswered notice is upheld, or a counter-notice
+	/// nobody resolved reinstates the SRC.
+	type TakedownPeriod: Get<Self::BlockNumber>;
+
+	/// Told when rights on an SRC are granted and when they revert.
+	type OnRightsChange: OnRightsChange<Self::AccountId>;
+
+	/// Told when a takedown notice is resolved or lapses.
+	type OnTakedownSettled: OnTakedownSettled<Self::AccountId>;
 }
 
+pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
 // Custom types
+/// Binary CIDv1 of the recording's audio file, see the [`cid`] module. Only
+/// `register_music` accepts new ids, after [`Module::validate_src_id`] parsed them with
+/// [`cid::Cid::parse`]; every other call looks the SRC up, so stored ids are valid CIDs.
 pub type SrcId = Vec<u8>;
 pub type SongId = Vec<u8>;
 pub type SongName = Vec<u8>;
@@ -39,10 +136,29 @@ pub type Composer = Vec<u8>;
 pub type Lyricist = Vec<u8>;
 pub type YOR = Vec<u8>;
 pub type Alias = Vec<u8>;
+pub type AlbumId = Vec<u8>;
+pub type CompositionId = Vec<u8>;
+pub type ArtistId = u64;
+pub type LicenseId = u64;
+pub type LanguageTag = Vec<u8>;
+/// Identifier of an entry in the genre taxonomy.
+pub type GenreId = u32;
+/// Identifier of an entry in the party registry.
+pub type PartyId = u64;
+/// International Standard Name Identifier: 15 digits and a MOD 11-2 check character.
+pub type Isni = [u8; 16];
+/// CISAC IPI name number: 9 digits and two modulus 101 check digits.
+pub type IpiNameNumber = [u8; 11];
+/// UPC-A (GTIN-12) or EAN-13 (GTIN-13) barcode digits, including the check digit.
+pub type Gtin = Vec<u8>;
+/// Label catalog number of a release, e.g. `WIGCD123`.
+pub type CatalogNumber = Vec<u8>;
+/// ISO 3166-1 alpha-2 country code, e.g. `*b"DE"`.
+pub type Territory = [u8; 2];
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
 pub struct MusicData<AccountId, Moment> {
-    // music file hash
+    // binary CIDv1 of the music file
     src_id: SrcId,
     
     // This is account that represents the ownership of the music created.
@@ -80,6 +196,35 @@ pub struct ArtistAlias {
     aliases: Alias
 }
 
+/// Artist entity owning a set of aliases. Its controller is the royalty
+/// recipient for every alias.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct Artist<AccountId> {
+    controller: AccountId,
+    // Canonical name of the artist, always one of `aliases`.
+    name: ArtistName,
+    aliases: Vec<Alias>,
+}
+
+/// Title and artist name of a catalog item in one language.
+///
+/// Transliterations are expressed through the script subtag of the language tag,
+/// e.g. `ja` for the original Japanese title and `ja-Latn` for its romanization.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct Localization {
+    language: LanguageTag,
+    title: Vec<u8>,
+    artist: ArtistName,
+}
+
+/// Per-language titles of an SRC or album, keyed by BCP-47 language tag.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct Localizations {
+    // Language tag of the entry to display when no better match is available.
+    default_language: LanguageTag,
+    entries: Vec<Localization>,
+}
+
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
 pub struct Album<Moment> {
     album_artist: ArtistName,
@@ -90,13 +235,72 @@ pub struct Album<Moment> {
     country_of_origin: Vec<u8>,
     display_label_name: Vec<u16>,
     explicit_: bool,
-    genre_1: u32,
+    genre_1: GenreId,
     master_label_name: Vec<u16>,
     p_line: Vec<u16>,
     part_of_album: bool,
     release_date: Moment,
     sales_start_date: Vec<u16>,
-    upc_or_ean: bool
+    upc_or_ean: Gtin,
+    catalog_number: CatalogNumber,
+}
+
+/// Genre id used by albums and tracks that do not declare a genre.
+pub const GENRE_UNSPECIFIED: GenreId = 0;
+
+/// Entry of the genre taxonomy, e.g. "Bebop" with "Jazz" as parent.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct Genre {
+    parent: Option<GenreId>,
+    name: Vec<u8>,
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ClearanceStatus {
+    Pending,
+    Cleared,
+    Rejected,
+}
+
+/// Declaration that an SRC samples another registered SRC.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct Sample {
+    // The sampled SRC.
+    src_id: SrcId,
+
+    // Royalty share of the sampling SRC owed to the sampled master once cleared.
+    share_bp: u32,
+
+    status: ClearanceStatus,
+}
+
+/// Commitment to the metadata of a release that must not be public before its release date.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct Embargo<Hash, Moment> {
+    // Hash of the SCALE encoded `(album, salt)` pair.
+    metadata_hash: Hash,
+
+    // Earliest timestamp at which the metadata may be revealed.
+    release_date: Moment,
+
+    // GTIN-13 of the album, reserved until the metadata is revealed.
+    gtin: [u8; 13],
+}
+
+/// Territories in which the rights on an SRC are held during a validity window.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct TerritoryRights<Moment> {
+    // Territories covered by the rights. An empty list stands for the whole world.
+    include: Vec<Territory>,
+
+    // Territories carved out of `include`.
+    exclude: Vec<Territory>,
+
+    // Start of the validity window (inclusive).
+    valid_from: Moment,
+
+    // End of the validity window (exclusive), `None` for perpetual rights.
+    valid_until: Option<Moment>,
 }
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
@@ -106,8 +310,8 @@ pub struct Track {
     track_title: Vec<u8>,
     track_volume: u32,
     track_duration: Vec<u32>,
-    genre_1: Vec<u8>,
-    genre_2: Vec<u8>,
+    genre_1: GenreId,
+    genre_2: GenreId,
     p_line: Vec<u8>,
     samples: bool,
     track_artists: Vec<ArtistName>,
@@ -125,6 +329,161 @@ pub struct Comp<Moment> {
     created: Moment,
 }
 
+/// A registered work an SRC can be derived from.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub enum Work {
+    Src(SrcId),
+    Composition(CompositionId),
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DerivativeKind {
+    // A recording of a composition.
+    Recording,
+    Remix,
+    Cover,
+    RadioEdit,
+    LiveVersion,
+}
+
+/// Typed edge from a derivative SRC to the work it is based on.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct Derivation {
+    original: Work,
+    kind: DerivativeKind,
+
+    // Set once the owner of the original work acknowledged the relationship.
+    acknowledged: bool,
+}
+
+/// Contributor roles from the DDEX `ResourceContributorRole` allowed value set.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub enum ContributorRole {
+    MainArtist,
+    FeaturedArtist,
+    Producer,
+    CoProducer,
+    ExecutiveProducer,
+    MixingEngineer,
+    MasteringEngineer,
+    RecordingEngineer,
+    Remixer,
+    Arranger,
+    Conductor,
+    Composer,
+    Lyricist,
+    // DDEX `Musician`, i.e. a session or band musician.
+    Musician,
+    Vocalist,
+    MusicPublisher,
+    // DDEX `UserDefined` role with its `UserDefinedValue`.
+    UserDefined(Vec<u8>),
+}
+
+/// Credit of a contributor on an SRC.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct Credit<AccountId> {
+    role: ContributorRole,
+    name: ArtistName,
+
+    // On-chain account of the contributor, if any.
+    account: Option<AccountId>,
+
+    // Registered party of the contributor, if any.
+    party: Option<PartyId>,
+
+    // Set by the linked account to prove the credit. Never taken from input.
+    confirmed: bool,
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PartyKind {
+    Artist,
+    Writer,
+    Publisher,
+}
+
+/// Artist, writer or publisher with its industry identifiers.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct Party<AccountId> {
+    kind: PartyKind,
+    name: Vec<u8>,
+
+    // Account allowed to update the party.
+    controller: AccountId,
+
+    isni: Option<Isni>,
+    ipi: Option<IpiNameNumber>,
+}
+
+/// Temporary transfer of the rights on an SRC, reverted when it expires.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct Grant<AccountId, BlockNumber> {
+    grantor: AccountId,
+    grantee: AccountId,
+    start: BlockNumber,
+
+    // Block at which the rights revert to the grantor.
+    expires: BlockNumber,
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LicenseType {
+    Sync,
+    Mechanical,
+    Streaming,
+    Sampling,
+}
+
+/// Usage license an SRC owner offers for sale.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct LicenseTemplate<Balance, BlockNumber> {
+    license_type: LicenseType,
+
+    // Territories covered by the license. An empty list stands for the whole world.
+    territories: Vec<Territory>,
+
+    // Number of blocks the license is valid for once purchased.
+    term: BlockNumber,
+
+    price: Balance,
+}
+
+/// License issued to a licensee on purchase of a template.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct License<AccountId, Balance, BlockNumber> {
+    src_id: SrcId,
+    licensor: AccountId,
+    licensee: AccountId,
+    license_type: LicenseType,
+    territories: Vec<Territory>,
+    price: Balance,
+    start: BlockNumber,
+    expires: BlockNumber,
+}
+
+/// Infringement notice filed against an SRC, which stays suspended while it is open.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct Takedown<AccountId, Balance, BlockNumber, Hash> {
+    claimant: AccountId,
+    evidence: Hash,
+    bond: Balance,
+    filed: BlockNumber,
+
+    // Block at which the notice resolves itself if nobody acts on it.
+    deadline: BlockNumber,
+
+    counter_notice: Option<CounterNotice<AccountId, Balance, Hash>>,
+}
+
+/// Owner's answer to a takedown notice.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct CounterNotice<AccountId, Balance, Hash> {
+    respondent: AccountId,
+    evidence: Hash,
+    bond: Balance,
+}
+
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
 pub struct DistributionsMaster {
 	payee: ArtistName,
@@ -169,136 +528,1988 @@ impl TestData {
     }
 }
 
-// The pallet's runtime storage items.
-// https://substrate.dev/docs/en/knowledgebase/runtime/storage
-decl_storage! {
-	trait Store for Module<T: Config> as RightsMgmtPallet {
-		pub MusicCollections get(fn music_by_src_id): map hasher(blake2_128_concat) SrcId => Option<MusicData<T::AccountId, T::Moment>>;
-        pub SrcCollections get(fn products_of_org): map hasher(blake2_128_concat) T::AccountId => Vec<SrcId>;
-        pub OwnerOf get(fn owner_of): map hasher(blake2_128_concat) SrcId => Option<T::AccountId>;
-	}
+impl<AccountId: PartialEq> Credit<AccountId> {
+    pub fn new(role: ContributorRole, name: &[u8], account: Option<AccountId>) -> Self {
+        Self {
+            role,
+            name: name.to_vec(),
+            account,
+            party: None,
+            confirmed: false,
+        }
+    }
+
+    pub fn with_party(mut self, party: PartyId) -> Self {
+        self.party = Some(party);
+        self
+    }
+
+    pub fn role(&self) -> &ContributorRole {
+        &self.role
+    }
+
+    pub fn name(&self) -> &[u8] {
+        self.name.as_ref()
+    }
+
+    pub fn account(&self) -> Option<&AccountId> {
+        self.account.as_ref()
+    }
+
+    pub fn party(&self) -> Option<PartyId> {
+        self.party
+    }
+
+    pub fn is_confirmed(&self) -> bool {
+        self.confirmed
+    }
+
+    fn same_contribution(&self, other: &Self) -> bool {
+        self.role == other.role
+            && self.name == other.name
+            && self.account == other.account
+            && self.party == other.party
+    }
 }
 
-// Pallets use events to inform users when important changes are made.
-// https://substrate.dev/docs/en/knowledgebase/runtime/events
-decl_event!(
-	pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId {
-		/// Event documentation should end with an array that provides descriptive names for event
-		SrcCreated(AccountId, SrcId, SongId, AccountId),
-	}
-);
+impl<AccountId> Artist<AccountId> {
+    pub fn controller(&self) -> &AccountId {
+        &self.controller
+    }
 
-// Errors inform users that something went wrong.
-decl_error! {
-	pub enum Error for Module<T: Config> {
-        SrcIdMissing,
-        SrcIdTooLong,
-        SrcIdExists,
-		SongIdMissing,
-        SongIdTooLong,
-        SongIdExists,
-        SongTooManyProps,
-        SongInvalidSongName,
-        SongInvalidArtistName,
-		SongInvalidComposer,
-		SongInvalidLyricist,
-		SongInvalidYOR
-	}
+    pub fn name(&self) -> &[u8] {
+        self.name.as_ref()
+    }
+
+    pub fn aliases(&self) -> &[Alias] {
+        self.aliases.as_ref()
+    }
 }
 
-decl_module! {
-	pub struct Module<T: Config> for enum Call where origin: T::Origin {
-		type Error = Error<T>;
-		fn deposit_event() = default;
+impl<AccountId> Party<AccountId> {
+    pub fn kind(&self) -> PartyKind {
+        self.kind
+    }
 
-		#[weight = 10_000]
-		pub fn register_music(origin, src_id: SrcId, song_id: SongId, owner: T::AccountId, props: Option<Vec<TestData>>) -> dispatch::DispatchResult {
-            
-            let who = ensure_signed(origin)?;
+    pub fn name(&self) -> &[u8] {
+        self.name.as_ref()
+    }
 
-            // Validate music file hash
-            Self::validate_src_id(&src_id)?;
+    pub fn controller(&self) -> &AccountId {
+        &self.controller
+    }
 
-            // Validate song ID
-            Self::validate_song_id(&song_id)?;
+    pub fn isni(&self) -> Option<&Isni> {
+        self.isni.as_ref()
+    }
 
-            // Validate song props
-            Self::validate_song_props(&props)?;
+    pub fn ipi(&self) -> Option<&IpiNameNumber> {
+        self.ipi.as_ref()
+    }
+}
 
-            // Check SRC doesn't exist yet (1 DB read)
-            Self::validate_new_src_id(&src_id)?;
+impl<AccountId, BlockNumber> Grant<AccountId, BlockNumber> {
+    pub fn grantor(&self) -> &AccountId {
+        &self.grantor
+    }
 
-            // Create a song instance
-            let song = Self::new_song()
-                .verified_by(src_id.clone())
-                .identified_by(Some(song_id.clone()))
-                .owned_by(owner.clone())
-                .registered_on(<timestamp::Module<T>>::get())
-                .with_props(props)
-                .build();
+    pub fn grantee(&self) -> &AccountId {
+        &self.grantee
+    }
 
-            <MusicCollections<T>>::insert(&src_id, song);
-            <SrcCollections<T>>::append(&owner, &src_id);
-            <OwnerOf<T>>::insert(&src_id, &owner);
+    pub fn start(&self) -> &BlockNumber {
+        &self.start
+    }
 
-            Self::deposit_event(RawEvent::SrcCreated(who, src_id, song_id, owner));
+    pub fn expires(&self) -> &BlockNumber {
+        &self.expires
+    }
+}
 
-            Ok(())
+impl<Balance, BlockNumber> LicenseTemplate<Balance, BlockNumber> {
+    pub fn new(license_type: LicenseType, territories: Vec<Territory>, term: BlockNumber, price: Balance) -> Self {
+        Self {
+            license_type,
+            territories,
+            term,
+            price,
         }
-	}
+    }
+
+    pub fn license_type(&self) -> LicenseType {
+        self.license_type
+    }
+
+    pub fn territories(&self) -> &[Territory] {
+        self.territories.as_ref()
+    }
+
+    pub fn term(&self) -> &BlockNumber {
+        &self.term
+    }
+
+    pub fn price(&self) -> &Balance {
+        &self.price
+    }
 }
 
-impl<T: Config> Module<T> {
-    
-    fn new_song() -> SongBuilder<T::AccountId, T::Moment> {
-        SongBuilder::<T::AccountId, T::Moment>::default()
+impl<AccountId: PartialEq, Balance, BlockNumber: PartialOrd> License<AccountId, Balance, BlockNumber> {
+    pub fn src_id(&self) -> &[u8] {
+        self.src_id.as_ref()
     }
 
-    pub fn validate_src_id(src_id: &[u8]) -> Result<(), Error<T>> {
-        // File Hash validation
-        ensure!(!src_id.is_empty(), Error::<T>::SrcIdMissing);
-        ensure!(
-            src_id.len() <= SRC_ID_MAX_LENGTH,
-            Error::<T>::SrcIdTooLong
-        );
-        Ok(())
+    pub fn licensor(&self) -> &AccountId {
+        &self.licensor
     }
 
-    pub fn validate_song_id(song_id: &[u8]) -> Result<(), Error<T>> {
-        // Basic song ID validation
-        ensure!(!song_id.is_empty(), Error::<T>::SongIdMissing);
-        ensure!(
-            song_id.len() <= SONG_ID_MAX_LENGTH,
-            Error::<T>::SongIdTooLong
-        );
-        Ok(())
+    pub fn licensee(&self) -> &AccountId {
+        &self.licensee
     }
 
-    pub fn validate_new_src_id(src_id: &[u8]) -> Result<(), Error<T>> {
-        // SRC existence check
-        ensure!(
-            !<MusicCollections<T>>::contains_key(src_id),
-            Error::<T>::SrcIdExists
-        );
-        Ok(())
+    pub fn license_type(&self) -> LicenseType {
+        self.license_type
     }
 
+    pub fn territories(&self) -> &[Territory] {
+        self.territories.as_ref()
+    }
 
-    pub fn validate_song_props(props: &Option<Vec<TestData>>) -> Result<(), Error<T>> {
-        if let Some(props) = props {
-            ensure!(
-                props.len() <= SONG_MAX_PROPS,
-                Error::<T>::SongTooManyProps,
-            );
-            for prop in props {
-                ensure!(
-                    prop.name().len() <= SONG_NAME_MAX_LENGTH,
-                    Error::<T>::SongInvalidSongName
-                );
-                ensure!(
-                    prop.artist().len() <= ARTIST_NAME_MAX_LENGTH,
-                    Error::<T>::SongInvalidArtistName
-                );
+    pub fn price(&self) -> &Balance {
+        &self.price
+    }
+
+    pub fn expires(&self) -> &BlockNumber {
+        &self.expires
+    }
+
+    /// Whether the license lets `licensee` use `src_id` for `license_type` in `territory` at block `now`.
+    pub fn permits(&self, licensee: &AccountId, src_id: &[u8], license_type: LicenseType, territory: &Territory, now: &BlockNumber) -> bool {
+        &self.licensee == licensee
+            && self.src_id() == src_id
+            && self.license_type == license_type
+            && (self.territories.is_empty() || self.territories.contains(territory))
+            && *now >= self.start
+            && *now < self.expires
+    }
+}
+
+impl<AccountId, Balance, BlockNumber, Hash> Takedown<AccountId, Balance, BlockNumber, Hash> {
+    pub fn claimant(&self) -> &AccountId {
+        &self.claimant
+    }
+
+    pub fn evidence(&self) -> &Hash {
+        &self.evidence
+    }
+
+    pub fn bond(&self) -> &Balance {
+        &self.bond
+    }
+
+    pub fn filed(&self) -> &BlockNumber {
+        &self.filed
+    }
+
+    pub fn deadline(&self) -> &BlockNumber {
+        &self.deadline
+    }
+
+    pub fn counter_notice(&self) -> Option<&CounterNotice<AccountId, Balance, Hash>> {
+        self.counter_notice.as_ref()
+    }
+}
+
+impl<AccountId, Balance, Hash> CounterNotice<AccountId, Balance, Hash> {
+    pub fn respondent(&self) -> &AccountId {
+        &self.respondent
+    }
+
+    pub fn evidence(&self) -> &Hash {
+        &self.evidence
+    }
+
+    pub fn bond(&self) -> &Balance {
+        &self.bond
+    }
+}
+
+impl Derivation {
+    pub fn original(&self) -> &Work {
+        &self.original
+    }
+
+    pub fn kind(&self) -> DerivativeKind {
+        self.kind
+    }
+
+    pub fn is_acknowledged(&self) -> bool {
+        self.acknowledged
+    }
+}
+
+impl<Moment> Album<Moment> {
+    pub fn release_date(&self) -> &Moment {
+        &self.release_date
+    }
+
+    pub fn genre(&self) -> GenreId {
+        self.genre_1
+    }
+
+    pub fn upc_or_ean(&self) -> &[u8] {
+        self.upc_or_ean.as_ref()
+    }
+
+    pub fn catalog_number(&self) -> &[u8] {
+        self.catalog_number.as_ref()
+    }
+}
+
+impl Genre {
+    pub fn new(parent: Option<GenreId>, name: &[u8]) -> Self {
+        Self {
+            parent,
+            name: name.to_vec(),
+        }
+    }
+
+    pub fn parent(&self) -> Option<GenreId> {
+        self.parent
+    }
+
+    pub fn name(&self) -> &[u8] {
+        self.name.as_ref()
+    }
+}
+
+impl Sample {
+    pub fn src_id(&self) -> &[u8] {
+        self.src_id.as_ref()
+    }
+
+    pub fn share_bp(&self) -> u32 {
+        self.share_bp
+    }
+
+    pub fn status(&self) -> ClearanceStatus {
+        self.status
+    }
+
+    pub fn is_cleared(&self) -> bool {
+        self.status == ClearanceStatus::Cleared
+    }
+}
+
+impl<Hash, Moment> Embargo<Hash, Moment> {
+    pub fn metadata_hash(&self) -> &Hash {
+        &self.metadata_hash
+    }
+
+    pub fn release_date(&self) -> &Moment {
+        &self.release_date
+    }
+
+    pub fn gtin(&self) -> &[u8; 13] {
+        &self.gtin
+    }
+}
+
+impl<Moment: PartialOrd> TerritoryRights<Moment> {
+    pub fn new(include: Vec<Territory>, exclude: Vec<Territory>, valid_from: Moment, valid_until: Option<Moment>) -> Self {
+        Self {
+            include,
+            exclude,
+            valid_from,
+            valid_until,
+        }
+    }
+
+    pub fn include(&self) -> &[Territory] {
+        self.include.as_ref()
+    }
+
+    pub fn exclude(&self) -> &[Territory] {
+        self.exclude.as_ref()
+    }
+
+    pub fn valid_from(&self) -> &Moment {
+        &self.valid_from
+    }
+
+    pub fn valid_until(&self) -> Option<&Moment> {
+        self.valid_until.as_ref()
+    }
+
+    /// Whether these rights cover `territory` at `time`.
+    pub fn covers(&self, territory: &Territory, time: &Moment) -> bool {
+        let in_window = *time >= self.valid_from
+            && self.valid_until.as_ref().map_or(true, |until| time < until);
+        let included = self.include.is_empty() || self.include.contains(territory);
+        in_window && included && !self.exclude.contains(territory)
+    }
+}
+
+impl Localization {
+    pub fn new(language: &[u8], title: &[u8], artist: &[u8]) -> Self {
+        Self {
+            language: language.to_vec(),
+            title: title.to_vec(),
+            artist: artist.to_vec(),
+        }
+    }
+
+    pub fn language(&self) -> &[u8] {
+        self.language.as_ref()
+    }
+
+    pub fn title(&self) -> &[u8] {
+        self.title.as_ref()
+    }
+
+    pub fn artist(&self) -> &[u8] {
+        self.artist.as_ref()
+    }
+}
+
+impl Localizations {
+    pub fn new(default_language: &[u8], entries: Vec<Localization>) -> Self {
+        Self {
+            default_language: default_language.to_vec(),
+            entries,
+        }
+    }
+
+    pub fn default_language(&self) -> &[u8] {
+        self.default_language.as_ref()
+    }
+
+    pub fn entries(&self) -> &[Localization] {
+        self.entries.as_ref()
+    }
+
+    /// Returns the entry for `language`, falling back to the default language.
+    pub fn get(&self, language: &[u8]) -> Option<&Localization> {
+        self.entries
+            .iter()
+            .find(|entry| entry.language() == language)
+            .or_else(|| self.entries.iter().find(|entry| entry.language() == self.default_language()))
+    }
+}
+
+// The pallet's runtime storage items.
+// https://substrate.dev/docs/en/knowledgebase/runtime/storage
+decl_storage! {
+	trait Store for Module<T: Config> as RightsMgmtPallet {
+		pub MusicCollections get(fn music_by_src_id): map hasher(blake2_128_concat) SrcId => Option<MusicData<T::AccountId, T::Moment>>;
+        pub SrcCollections get(fn products_of_org): map hasher(blake2_128_concat) T::AccountId => Vec<SrcId>;
+        pub OwnerOf get(fn owner_of): map hasher(blake2_128_concat) SrcId => Option<T::AccountId>;
+        pub Albums get(fn album_by_id): map hasher(blake2_128_concat) AlbumId => Option<Album<T::Moment>>;
+        pub AlbumOwnerOf get(fn album_owner_of): map hasher(blake2_128_concat) AlbumId => Option<T::AccountId>;
+        // SRCs on an album, in track order.
+        pub AlbumTracks get(fn album_tracks): map hasher(blake2_128_concat) AlbumId => Vec<SrcId>;
+        pub SrcTerritoryRights get(fn territory_rights_of): map hasher(blake2_128_concat) SrcId => Vec<TerritoryRights<T::Moment>>;
+        pub Genres get(fn genre): map hasher(twox_64_concat) GenreId => Option<Genre>;
+        pub GenreChildren get(fn genre_children): map hasher(twox_64_concat) GenreId => Vec<GenreId>;
+        pub SrcGenres get(fn genres_of_src): map hasher(blake2_128_concat) SrcId => Vec<GenreId>;
+        pub SrcsByGenre get(fn srcs_by_genre): map hasher(twox_64_concat) GenreId => Vec<SrcId>;
+        pub SrcSamples get(fn samples_of): map hasher(blake2_128_concat) SrcId => Vec<Sample>;
+        pub SampledBy get(fn sampled_by): map hasher(blake2_128_concat) SrcId => Vec<SrcId>;
+        pub ContainsSamples get(fn contains_samples): map hasher(blake2_128_concat) SrcId => bool;
+        pub Compositions get(fn composition_by_id): map hasher(blake2_128_concat) CompositionId => Option<Comp<T::Moment>>;
+        pub CompositionOwnerOf get(fn composition_owner_of): map hasher(blake2_128_concat) CompositionId => Option<T::AccountId>;
+        pub DerivedFrom get(fn derived_from): map hasher(blake2_128_concat) SrcId => Vec<Derivation>;
+        pub DerivativesOf get(fn derivatives_of): map hasher(blake2_128_concat) Work => Vec<SrcId>;
+        pub SrcCredits get(fn credits_of): map hasher(blake2_128_concat) SrcId => Vec<Credit<T::AccountId>>;
+        pub CreditedSrcs get(fn credited_srcs): map hasher(blake2_128_concat) T::AccountId => Vec<SrcId>;
+        pub CompositionCredits get(fn composition_credits_of): map hasher(blake2_128_concat) CompositionId => Vec<Credit<T::AccountId>>;
+        pub NextPartyId get(fn next_party_id): PartyId;
+        pub Parties get(fn party): map hasher(twox_64_concat) PartyId => Option<Party<T::AccountId>>;
+        pub PartyByIsni get(fn party_by_isni): map hasher(blake2_128_concat) Isni => Option<PartyId>;
+        pub PartyByIpi get(fn party_by_ipi): map hasher(blake2_128_concat) IpiNameNumber => Option<PartyId>;
+        pub AlbumByGtin get(fn album_id_by_gtin): map hasher(blake2_128_concat) [u8; 13] => Option<AlbumId>;
+        pub NextArtistId get(fn next_artist_id): ArtistId;
+        pub Artists get(fn artist): map hasher(twox_64_concat) ArtistId => Option<Artist<T::AccountId>>;
+        pub AliasOwner get(fn alias_owner): map hasher(blake2_128_concat) Alias => Option<ArtistId>;
+        // Live artist each retired artist resolves to, always a single hop.
+        pub MergedInto get(fn merged_into): map hasher(twox_64_concat) ArtistId => Option<ArtistId>;
+        // Retired artists resolving to a live artist, the reverse of `MergedInto`.
+        pub MergedFrom get(fn merged_from): map hasher(twox_64_concat) ArtistId => Vec<ArtistId>;
+        pub GrantChain get(fn grant_chain): map hasher(blake2_128_concat) SrcId => Vec<Grant<T::AccountId, T::BlockNumber>>;
+        pub GrantExpiries get(fn grant_expiries): map hasher(twox_64_concat) T::BlockNumber => Vec<SrcId>;
+        pub LicenseTemplates get(fn license_templates_of): map hasher(blake2_128_concat) SrcId => Vec<LicenseTemplate<BalanceOf<T>, T::BlockNumber>>;
+        // Templates offered before each grant in the chain, restored when that grant reverts.
+        pub GrantedTemplates get(fn granted_templates): map hasher(blake2_128_concat) SrcId => Vec<Vec<LicenseTemplate<BalanceOf<T>, T::BlockNumber>>>;
+        pub NextLicenseId get(fn next_license_id): LicenseId;
+        pub Licenses get(fn license): map hasher(twox_64_concat) LicenseId => Option<License<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+        pub LicensesOf get(fn licenses_of): map hasher(blake2_128_concat) T::AccountId => Vec<LicenseId>;
+        pub Takedowns get(fn takedown_of): map hasher(blake2_128_concat) SrcId => Option<Takedown<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>>;
+        pub TakedownDeadlines get(fn takedown_deadlines): map hasher(twox_64_concat) T::BlockNumber => Vec<SrcId>;
+        pub Suspended get(fn is_suspended): map hasher(blake2_128_concat) SrcId => bool;
+        pub Embargoes get(fn embargo_of): map hasher(blake2_128_concat) AlbumId => Option<Embargo<T::Hash, T::Moment>>;
+        pub SrcLocalizations get(fn src_localizations): map hasher(blake2_128_concat) SrcId => Option<Localizations>;
+        pub AlbumLocalizations get(fn album_localizations): map hasher(blake2_128_concat) AlbumId => Option<Localizations>;
+	}
+}
+
+// Pallets use events to inform users when important changes are made.
+// https://substrate.dev/docs/en/knowledgebase/runtime/events
+decl_event!(
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Config>::AccountId,
+		Balance = BalanceOf<T>,
+		BlockNumber = <T as frame_system::Config>::BlockNumber,
+		Hash = <T as frame_system::Config>::Hash,
+		Moment = <T as timestamp::Config>::Moment,
+	{
+		/// Event documentation should end with an array that provides descriptive names for event
+		SrcCreated(AccountId, SrcId, SongId, AccountId),
+		/// An album was registered. [who, album_id, owner]
+		AlbumCreated(AccountId, AlbumId, AccountId),
+		/// An album was registered with its metadata under embargo. [who, album_id, owner, release_date]
+		AlbumEmbargoed(AccountId, AlbumId, AccountId, Moment),
+		/// The metadata of an embargoed album was revealed. [who, album_id]
+		AlbumRevealed(AccountId, AlbumId),
+		/// The track list of an album was replaced. [who, album_id]
+		AlbumTracksSet(AccountId, AlbumId),
+		/// The territory rights of an SRC were replaced. [who, src_id]
+		TerritoryRightsSet(AccountId, SrcId),
+		/// A genre taxonomy entry was created or updated. [genre_id]
+		GenreSet(GenreId),
+		/// The genres of an SRC were replaced. [who, src_id]
+		SrcGenresSet(AccountId, SrcId),
+		/// An SRC was flagged as containing samples or not. [who, src_id, contains_samples]
+		ContainsSamplesSet(AccountId, SrcId, bool),
+		/// An SRC declared that it samples another SRC. [who, src_id, sampled_src_id, share_bp]
+		SampleDeclared(AccountId, SrcId, SrcId, u32),
+		/// The owner of a sampled SRC cleared the sample. [who, src_id, sampled_src_id]
+		SampleCleared(AccountId, SrcId, SrcId),
+		/// The owner of a sampled SRC rejected the sample. [who, src_id, sampled_src_id]
+		SampleRejected(AccountId, SrcId, SrcId),
+		/// A composition was registered. [who, composition_id, owner]
+		CompositionCreated(AccountId, CompositionId, AccountId),
+		/// An SRC declared that it derives from another work. [who, src_id, original, kind]
+		DerivativeDeclared(AccountId, SrcId, Work, DerivativeKind),
+		/// The owner of the original work acknowledged a derivative. [who, src_id, original]
+		DerivativeAcknowledged(AccountId, SrcId, Work),
+		/// The credits of an SRC were replaced. [who, src_id]
+		CreditsSet(AccountId, SrcId),
+		/// A contributor confirmed their credit on an SRC. [who, src_id, credit_index]
+		CreditConfirmed(AccountId, SrcId, u32),
+		/// The credits of a composition were replaced. [who, composition_id]
+		CompositionCreditsSet(AccountId, CompositionId),
+		/// A contributor confirmed their credit on a composition. [who, composition_id, credit_index]
+		CompositionCreditConfirmed(AccountId, CompositionId, u32),
+		/// A party was registered. [who, party_id]
+		PartyRegistered(AccountId, PartyId),
+		/// The identifiers of a party were replaced. [who, party_id]
+		PartyIdentifiersSet(AccountId, PartyId),
+		/// An artist was created with its canonical name. [who, artist_id]
+		ArtistCreated(AccountId, ArtistId),
+		/// An alias was claimed by an artist. [artist_id, alias]
+		AliasAdded(ArtistId, Alias),
+		/// An alias was released by an artist. [artist_id, alias]
+		AliasRemoved(ArtistId, Alias),
+		/// An artist was merged into another one. [from, into]
+		ArtistsMerged(ArtistId, ArtistId),
+		/// Aliases were split off an artist into a new one. [from, new_artist_id]
+		ArtistSplit(ArtistId, ArtistId),
+		/// The rights on an SRC were granted until a block. [grantor, src_id, grantee, expires]
+		RightsGranted(AccountId, SrcId, AccountId, BlockNumber),
+		/// An expired grant reverted the rights on an SRC. [src_id, from, to]
+		RightsReverted(SrcId, AccountId, AccountId),
+		/// The license templates of an SRC were replaced. [who, src_id]
+		LicenseTemplatesSet(AccountId, SrcId),
+		/// A license was purchased and paid to the owner. [licensee, src_id, license_id, price]
+		LicensePurchased(AccountId, SrcId, LicenseId, Balance),
+		/// A takedown notice was filed and the SRC suspended. [claimant, src_id, evidence]
+		TakedownFiled(AccountId, SrcId, Hash),
+		/// The owner answered a takedown notice. [respondent, src_id, evidence]
+		CounterNoticeFiled(AccountId, SrcId, Hash),
+		/// A takedown notice was upheld; the SRC stays suspended. [src_id]
+		TakedownUpheld(SrcId),
+		/// A takedown notice was dismissed and the SRC reinstated. [src_id]
+		TakedownDismissed(SrcId),
+		/// A suspended SRC was reinstated after an upheld notice. [src_id]
+		SrcReinstated(SrcId),
+		/// Localized titles of an SRC were replaced. [who, src_id]
+		SrcLocalized(AccountId, SrcId),
+		/// Localized titles of an album were replaced. [who, album_id]
+		AlbumLocalized(AccountId, AlbumId),
+	}
+);
+
+// Errors inform users that something went wrong.
+decl_error! {
+	pub enum Error for Module<T: Config> {
+        SrcIdMissing,
+        SrcIdTooLong,
+        SrcIdExists,
+        SrcIdInvalidCid,
+        SrcIdUnsupportedCidVersion,
+        SrcIdUnsupportedCodec,
+        SrcIdUnsupportedHash,
+		SongIdMissing,
+        SongIdTooLong,
+        SongIdExists,
+        SongTooManyProps,
+        SongInvalidSongName,
+        SongInvalidArtistName,
+		SongInvalidComposer,
+		SongInvalidLyricist,
+		SongInvalidYOR,
+        SrcNotFound,
+        NotSrcOwner,
+        AlbumIdMissing,
+        AlbumIdTooLong,
+        AlbumIdExists,
+        AlbumNotFound,
+        NotAlbumOwner,
+        AlbumTooManyTracks,
+        AlbumDuplicateTrack,
+        LocalizationTooManyEntries,
+        LocalizationInvalidLanguageTag,
+        LocalizationDuplicateLanguage,
+        LocalizationMissingDefault,
+        LocalizationInvalidTitle,
+        LocalizationInvalidArtist,
+        AlbumEmbargoed,
+        EmbargoNotFound,
+        EmbargoNotExpired,
+        EmbargoHashMismatch,
+        EmbargoGtinMismatch,
+        TerritoryRightsTooManyEntries,
+        TerritoryListTooLong,
+        TerritoryInvalidCode,
+        TerritoryInvalidWindow,
+        GenreInvalidId,
+        GenreInvalidName,
+        GenreNotFound,
+        GenreParentNotFound,
+        GenreCycle,
+        GenreTooDeep,
+        SrcTooManyGenres,
+        SrcDuplicateGenre,
+        SampleOfItself,
+        SampleAlreadyDeclared,
+        SampleNotFound,
+        SampleTooMany,
+        SampleSharesTooHigh,
+        SampleAlreadyAnswered,
+        CompositionIdMissing,
+        CompositionIdTooLong,
+        CompositionIdExists,
+        CompositionNotFound,
+        NotCompositionOwner,
+        NotWorkOwner,
+        DerivativeOfItself,
+        DerivationAlreadyDeclared,
+        DerivationNotFound,
+        DerivationTooMany,
+        DerivationCycle,
+        DerivationGraphTooLarge,
+        CreditTooMany,
+        CreditInvalidName,
+        CreditInvalidRole,
+        CreditNotFound,
+        NotCreditedAccount,
+        PartyNotFound,
+        NotPartyController,
+        PartyInvalidName,
+        InvalidIsni,
+        InvalidIpi,
+        IsniAlreadyClaimed,
+        IpiAlreadyClaimed,
+        InvalidGtin,
+        GtinExists,
+        InvalidCatalogNumber,
+        ArtistNotFound,
+        NotArtistController,
+        ArtistTooManyAliases,
+        AliasInvalid,
+        AliasTaken,
+        AliasNotFound,
+        AliasIsCanonicalName,
+        MergeIntoItself,
+        GrantToSelf,
+        GrantDurationZero,
+        GrantExceedsParent,
+        GrantChainTooLong,
+        GrantScheduleFull,
+        LicenseTemplatesTooMany,
+        LicenseTemplateNotFound,
+        LicenseInvalidTerm,
+        LicensePriceTooHigh,
+        SrcSuspended,
+        SrcNotSuspended,
+        TakedownPending,
+        TakedownNotFound,
+        TakedownScheduleFull,
+        TakedownDeadlinePassed,
+        CounterNoticeExists
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Config> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+		fn deposit_event() = default;
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let expiring = <GrantExpiries<T>>::take(now);
+			let mut reverted = 0u64;
+			let mut weight: Weight = 0;
+			for src_id in &expiring {
+				let (count, hook_weight) = Self::revert_expired_grants(src_id, now);
+				reverted += count;
+				weight = weight.saturating_add(hook_weight);
+			}
+			let lapsing = <TakedownDeadlines<T>>::take(now);
+			let mut settled = 0u64;
+			for src_id in &lapsing {
+				if let Some(hook_weight) = Self::lapse_takedown(src_id, now) {
+					settled += 1;
+					weight = weight.saturating_add(hook_weight);
+				}
+			}
+			weight.saturating_add(T::DbWeight::get().reads_writes(
+				2 + 2 * expiring.len() as u64 + 2 * lapsing.len() as u64,
+				2 + 5 * reverted + 4 * settled,
+			))
+		}
+
+		#[weight = 10_000]
+		pub fn register_music(origin, src_id: SrcId, song_id: SongId, owner: T::AccountId, props: Option<Vec<TestData>>) -> dispatch::DispatchResult {
+            
+            let who = ensure_signed(origin)?;
+
+            // Validate music file hash
+            Self::validate_src_id(&src_id)?;
+
+            // Validate song ID
+            Self::validate_song_id(&song_id)?;
+
+            // Validate song props
+            Self::validate_song_props(&props)?;
+
+            // Check SRC doesn't exist yet (1 DB read)
+            Self::validate_new_src_id(&src_id)?;
+
+            // Create a song instance
+            let song = Self::new_song()
+                .verified_by(src_id.clone())
+                .identified_by(Some(song_id.clone()))
+                .owned_by(owner.clone())
+                .registered_on(<timestamp::Module<T>>::get())
+                .with_props(props)
+                .build();
+
+            <MusicCollections<T>>::insert(&src_id, song);
+            <SrcCollections<T>>::append(&owner, &src_id);
+            <OwnerOf<T>>::insert(&src_id, &owner);
+
+            Self::deposit_event(RawEvent::SrcCreated(who, src_id, song_id, owner));
+
+            Ok(())
+        }
+
+		/// Register an album under `album_id`, owned by `owner`.
+		#[weight = 10_000]
+		pub fn register_album(origin, album_id: AlbumId, owner: T::AccountId, album: Album<T::Moment>) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::validate_album_id(&album_id)?;
+            Self::validate_new_album_id(&album_id)?;
+            Self::validate_album_genre(album.genre())?;
+            let gtin = Self::validate_album_codes(&album)?;
+            ensure!(!AlbumByGtin::contains_key(gtin), Error::<T>::GtinExists);
+
+            AlbumByGtin::insert(gtin, &album_id);
+            <Albums<T>>::insert(&album_id, album);
+            <AlbumOwnerOf<T>>::insert(&album_id, &owner);
+
+            Self::deposit_event(RawEvent::AlbumCreated(who, album_id, owner));
+
+            Ok(())
+        }
+
+		/// Register an album whose metadata stays hidden until `release_date`.
+		///
+		/// Only `metadata_hash`, the hash of the SCALE encoded `(album, salt)` pair, is stored.
+		/// The salt keeps guessable titles from being confirmed against the hash. The UPC or
+		/// EAN of the album is reserved in the clear so no other album can take it meanwhile.
+		#[weight = 10_000]
+		pub fn register_embargoed_album(origin, album_id: AlbumId, owner: T::AccountId, upc_or_ean: Gtin, metadata_hash: T::Hash, release_date: T::Moment) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::validate_album_id(&album_id)?;
+            Self::validate_new_album_id(&album_id)?;
+            let gtin = normalize_gtin(&upc_or_ean).ok_or(Error::<T>::InvalidGtin)?;
+            ensure!(!AlbumByGtin::contains_key(gtin), Error::<T>::GtinExists);
+
+            AlbumByGtin::insert(gtin, &album_id);
+            <Embargoes<T>>::insert(&album_id, Embargo { metadata_hash, release_date: release_date.clone(), gtin });
+            <AlbumOwnerOf<T>>::insert(&album_id, &owner);
+
+            Self::deposit_event(RawEvent::AlbumEmbargoed(who, album_id, owner, release_date));
+
+            Ok(())
+        }
+
+		/// Publish the metadata of an embargoed album at or after its release date.
+		/// Only the album owner may call this.
+		#[weight = 10_000]
+		pub fn reveal(origin, album_id: AlbumId, album: Album<T::Moment>, salt: Vec<u8>) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_album_owner(&who, &album_id)?;
+            let embargo = <Embargoes<T>>::get(&album_id).ok_or(Error::<T>::EmbargoNotFound)?;
+            ensure!(
+                <timestamp::Module<T>>::get() >= embargo.release_date,
+                Error::<T>::EmbargoNotExpired
+            );
+            ensure!(
+                T::Hashing::hash_of(&(&album, &salt)) == embargo.metadata_hash,
+                Error::<T>::EmbargoHashMismatch
+            );
+            Self::validate_album_genre(album.genre())?;
+            let gtin = Self::validate_album_codes(&album)?;
+            ensure!(gtin == embargo.gtin, Error::<T>::EmbargoGtinMismatch);
+
+            <Embargoes<T>>::remove(&album_id);
+            <Albums<T>>::insert(&album_id, album);
+
+            Self::deposit_event(RawEvent::AlbumRevealed(who, album_id));
+
+            Ok(())
+        }
+
+		/// Replace the territory rights of an SRC. Only the SRC owner may call this.
+		#[weight = 10_000]
+		pub fn set_territory_rights(origin, src_id: SrcId, rights: Vec<TerritoryRights<T::Moment>>) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_src_owner(&who, &src_id)?;
+            Self::validate_territory_rights(&rights)?;
+
+            <SrcTerritoryRights<T>>::insert(&src_id, rights);
+
+            Self::deposit_event(RawEvent::TerritoryRightsSet(who, src_id));
+
+            Ok(())
+        }
+
+		/// Create or update a genre taxonomy entry.
+		#[weight = 10_000]
+		pub fn set_genre(origin, genre_id: GenreId, genre: Genre) -> dispatch::DispatchResult {
+            T::GenreOrigin::ensure_origin(origin)?;
+
+            Self::validate_genre(genre_id, &genre)?;
+
+            let old_parent = Genres::get(genre_id).and_then(|old| old.parent());
+            if old_parent != genre.parent() {
+                if let Some(parent) = old_parent {
+                    GenreChildren::mutate(parent, |children| children.retain(|id| *id != genre_id));
+                }
+                if let Some(parent) = genre.parent() {
+                    GenreChildren::append(parent, genre_id);
+                }
+            }
+            Genres::insert(genre_id, genre);
+
+            Self::deposit_event(RawEvent::GenreSet(genre_id));
+
+            Ok(())
+        }
+
+		/// Replace the genres of an SRC. Only the SRC owner may call this.
+		#[weight = 10_000]
+		pub fn set_src_genres(origin, src_id: SrcId, genres: Vec<GenreId>) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_src_owner(&who, &src_id)?;
+            ensure!(genres.len() <= SRC_MAX_GENRES, Error::<T>::SrcTooManyGenres);
+            for (i, genre_id) in genres.iter().enumerate() {
+                ensure!(Genres::contains_key(genre_id), Error::<T>::GenreNotFound);
+                ensure!(!genres[..i].contains(genre_id), Error::<T>::SrcDuplicateGenre);
+            }
+
+            for genre_id in SrcGenres::get(&src_id) {
+                SrcsByGenre::mutate(genre_id, |srcs| srcs.retain(|id| id != &src_id));
+            }
+            for genre_id in &genres {
+                SrcsByGenre::append(genre_id, &src_id);
+            }
+            SrcGenres::insert(&src_id, genres);
+
+            Self::deposit_event(RawEvent::SrcGenresSet(who, src_id));
+
+            Ok(())
+        }
+
+		/// Flag whether an SRC contains samples, declared or not. Only the SRC owner may call this.
+		#[weight = 10_000]
+		pub fn set_contains_samples(origin, src_id: SrcId, contains_samples: bool) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_src_owner(&who, &src_id)?;
+
+            ContainsSamples::insert(&src_id, contains_samples);
+
+            Self::deposit_event(RawEvent::ContainsSamplesSet(who, src_id, contains_samples));
+
+            Ok(())
+        }
+
+		/// Declare that `src_id` samples `sampled_src_id` and request its clearance,
+		/// offering `share_bp` basis points of the royalties of `src_id` to the sampled master.
+		/// Only the owner of `src_id` may call this.
+		#[weight = 10_000]
+		pub fn declare_sample(origin, src_id: SrcId, sampled_src_id: SrcId, share_bp: u32) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_src_owner(&who, &src_id)?;
+            ensure!(src_id != sampled_src_id, Error::<T>::SampleOfItself);
+            ensure!(<OwnerOf<T>>::contains_key(&sampled_src_id), Error::<T>::SrcNotFound);
+
+            let mut samples = SrcSamples::get(&src_id);
+            ensure!(samples.len() < SRC_MAX_SAMPLES, Error::<T>::SampleTooMany);
+            ensure!(
+                !samples.iter().any(|sample| sample.src_id() == &sampled_src_id[..]),
+                Error::<T>::SampleAlreadyDeclared
+            );
+            let total_bp = samples.iter().fold(share_bp, |total, sample| total.saturating_add(sample.share_bp()));
+            ensure!(total_bp <= MAX_BASIS_POINTS, Error::<T>::SampleSharesTooHigh);
+
+            samples.push(Sample { src_id: sampled_src_id.clone(), share_bp, status: ClearanceStatus::Pending });
+            SrcSamples::insert(&src_id, samples);
+            SampledBy::append(&sampled_src_id, &src_id);
+            ContainsSamples::insert(&src_id, true);
+
+            Self::deposit_event(RawEvent::SampleDeclared(who, src_id, sampled_src_id, share_bp));
+
+            Ok(())
+        }
+
+		/// Approve or reject the use of `sampled_src_id` in `src_id`. The answer is final, since
+		/// royalties are split along it. Only the owner of the sampled SRC may call this.
+		#[weight = 10_000]
+		pub fn respond_to_sample(origin, src_id: SrcId, sampled_src_id: SrcId, approve: bool) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_src_owner(&who, &sampled_src_id)?;
+
+            SrcSamples::try_mutate(&src_id, |samples| -> Result<(), Error<T>> {
+                let sample = samples
+                    .iter_mut()
+                    .find(|sample| sample.src_id() == &sampled_src_id[..])
+                    .ok_or(Error::<T>::SampleNotFound)?;
+                ensure!(sample.status == ClearanceStatus::Pending, Error::<T>::SampleAlreadyAnswered);
+                sample.status = if approve { ClearanceStatus::Cleared } else { ClearanceStatus::Rejected };
+                Ok(())
+            })?;
+
+            if approve {
+                Self::deposit_event(RawEvent::SampleCleared(who, src_id, sampled_src_id));
+            } else {
+                Self::deposit_event(RawEvent::SampleRejected(who, src_id, sampled_src_id));
+            }
+
+            Ok(())
+        }
+
+		/// Register a composition under `composition_id`, typically its ISWC.
+		#[weight = 10_000]
+		pub fn register_composition(origin, composition_id: CompositionId, owner: T::AccountId, comp: Comp<T::Moment>) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(!composition_id.is_empty(), Error::<T>::CompositionIdMissing);
+            ensure!(
+                composition_id.len() <= COMPOSITION_ID_MAX_LENGTH,
+                Error::<T>::CompositionIdTooLong
+            );
+            ensure!(
+                !<Compositions<T>>::contains_key(&composition_id),
+                Error::<T>::CompositionIdExists
+            );
+
+            <Compositions<T>>::insert(&composition_id, comp);
+            <CompositionOwnerOf<T>>::insert(&composition_id, &owner);
+
+            Self::deposit_event(RawEvent::CompositionCreated(who, composition_id, owner));
+
+            Ok(())
+        }
+
+		/// Declare that `src_id` is a remix, cover, edit, ... of `original`.
+		/// Only the SRC owner may call this.
+		#[weight = 10_000]
+		pub fn declare_derivative(origin, src_id: SrcId, original: Work, kind: DerivativeKind) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_src_owner(&who, &src_id)?;
+            ensure!(original != Work::Src(src_id.clone()), Error::<T>::DerivativeOfItself);
+            Self::work_owner(&original)?;
+            ensure!(!Self::derives_from(&original, &src_id)?, Error::<T>::DerivationCycle);
+
+            let mut derivations = DerivedFrom::get(&src_id);
+            ensure!(derivations.len() < SRC_MAX_DERIVATIONS, Error::<T>::DerivationTooMany);
+            ensure!(
+                !derivations.iter().any(|derivation| derivation.original() == &original),
+                Error::<T>::DerivationAlreadyDeclared
+            );
+
+            derivations.push(Derivation { original: original.clone(), kind, acknowledged: false });
+            DerivedFrom::insert(&src_id, derivations);
+            DerivativesOf::append(&original, &src_id);
+
+            Self::deposit_event(RawEvent::DerivativeDeclared(who, src_id, original, kind));
+
+            Ok(())
+        }
+
+		/// Acknowledge that `src_id` derives from `original`. Only the owner of the original may call this.
+		#[weight = 10_000]
+		pub fn acknowledge_derivative(origin, src_id: SrcId, original: Work) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let owner = Self::work_owner(&original)?;
+            ensure!(owner == who, Error::<T>::NotWorkOwner);
+
+            DerivedFrom::try_mutate(&src_id, |derivations| -> Result<(), Error<T>> {
+                let derivation = derivations
+                    .iter_mut()
+                    .find(|derivation| derivation.original() == &original)
+                    .ok_or(Error::<T>::DerivationNotFound)?;
+                derivation.acknowledged = true;
+                Ok(())
+            })?;
+
+            Self::deposit_event(RawEvent::DerivativeAcknowledged(who, src_id, original));
+
+            Ok(())
+        }
+
+		/// Replace the contributor credits of an SRC. Only the SRC owner may call this.
+		///
+		/// Confirmations are kept for credits that are unchanged, all others must be
+		/// confirmed again by their linked account.
+		#[weight = 10_000]
+		pub fn set_credits(origin, src_id: SrcId, credits: Vec<Credit<T::AccountId>>) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_src_owner(&who, &src_id)?;
+            Self::validate_credits(&credits)?;
+
+            let previous = <SrcCredits<T>>::get(&src_id);
+            let credits: Vec<_> = credits
+                .into_iter()
+                .map(|mut credit| {
+                    credit.confirmed = previous
+                        .iter()
+                        .any(|old| old.is_confirmed() && old.same_contribution(&credit));
+                    credit
+                })
+                .collect();
+
+            for account in previous.iter().filter_map(Credit::account) {
+                <CreditedSrcs<T>>::mutate(account, |srcs| srcs.retain(|id| id != &src_id));
+            }
+            for account in credits.iter().filter_map(Credit::account) {
+                <CreditedSrcs<T>>::mutate(account, |srcs| {
+                    if !srcs.contains(&src_id) {
+                        srcs.push(src_id.clone());
+                    }
+                });
+            }
+            <SrcCredits<T>>::insert(&src_id, credits);
+
+            Self::deposit_event(RawEvent::CreditsSet(who, src_id));
+
+            Ok(())
+        }
+
+		/// Replace the credits of a composition. Only the composition owner may call this.
+		///
+		/// As for SRC credits, confirmations are kept for credits that are unchanged.
+		#[weight = 10_000]
+		pub fn set_composition_credits(origin, composition_id: CompositionId, credits: Vec<Credit<T::AccountId>>) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let owner = <CompositionOwnerOf<T>>::get(&composition_id).ok_or(Error::<T>::CompositionNotFound)?;
+            ensure!(owner == who, Error::<T>::NotCompositionOwner);
+            Self::validate_credits(&credits)?;
+
+            let previous = <CompositionCredits<T>>::get(&composition_id);
+            let credits: Vec<_> = credits
+                .into_iter()
+                .map(|mut credit| {
+                    credit.confirmed = previous
+                        .iter()
+                        .any(|old| old.is_confirmed() && old.same_contribution(&credit));
+                    credit
+                })
+                .collect();
+            <CompositionCredits<T>>::insert(&composition_id, credits);
+
+            Self::deposit_event(RawEvent::CompositionCreditsSet(who, composition_id));
+
+            Ok(())
+        }
+
+		/// Register an artist, writer or publisher controlled by the caller.
+		#[weight = 10_000]
+		pub fn register_party(origin, kind: PartyKind, name: Vec<u8>, isni: Option<Isni>, ipi: Option<IpiNameNumber>) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                !name.is_empty() && name.len() <= PARTY_NAME_MAX_LENGTH,
+                Error::<T>::PartyInvalidName
+            );
+            let party_id = NextPartyId::get();
+            Self::claim_identifiers(party_id, isni.as_ref(), ipi.as_ref())?;
+
+            <Parties<T>>::insert(party_id, Party { kind, name, controller: who.clone(), isni, ipi });
+            NextPartyId::put(party_id + 1);
+
+            Self::deposit_event(RawEvent::PartyRegistered(who, party_id));
+
+            Ok(())
+        }
+
+		/// Replace the ISNI and IPI name number of a party. Only its controller may call this.
+		#[weight = 10_000]
+		pub fn set_party_identifiers(origin, party_id: PartyId, isni: Option<Isni>, ipi: Option<IpiNameNumber>) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut party = <Parties<T>>::get(party_id).ok_or(Error::<T>::PartyNotFound)?;
+            ensure!(party.controller == who, Error::<T>::NotPartyController);
+            Self::claim_identifiers(party_id, isni.as_ref(), ipi.as_ref())?;
+
+            if let Some(old) = party.isni.filter(|old| Some(old) != isni.as_ref()) {
+                PartyByIsni::remove(old);
+            }
+            if let Some(old) = party.ipi.filter(|old| Some(old) != ipi.as_ref()) {
+                PartyByIpi::remove(old);
+            }
+            party.isni = isni;
+            party.ipi = ipi;
+            <Parties<T>>::insert(party_id, party);
+
+            Self::deposit_event(RawEvent::PartyIdentifiersSet(who, party_id));
+
+            Ok(())
+        }
+
+		/// Create an artist controlled by the caller, claiming `name` as its canonical alias.
+		#[weight = 10_000]
+		pub fn create_artist(origin, name: ArtistName) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let artist_id = Self::insert_artist(who.clone(), name)?;
+
+            Self::deposit_event(RawEvent::ArtistCreated(who, artist_id));
+
+            Ok(())
+        }
+
+		/// Claim `alias` for an artist. Only the artist controller may call this.
+		#[weight = 10_000]
+		pub fn add_alias(origin, artist_id: ArtistId, alias: Alias) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut artist = Self::controlled_artist(&who, artist_id)?;
+            ensure!(artist.aliases.len() < ARTIST_MAX_ALIASES, Error::<T>::ArtistTooManyAliases);
+            Self::claim_alias(artist_id, &alias)?;
+
+            artist.aliases.push(alias.clone());
+            <Artists<T>>::insert(artist_id, artist);
+
+            Self::deposit_event(RawEvent::AliasAdded(artist_id, alias));
+
+            Ok(())
+        }
+
+		/// Release `alias` of an artist. The canonical name cannot be released.
+		/// Only the artist controller may call this.
+		#[weight = 10_000]
+		pub fn remove_alias(origin, artist_id: ArtistId, alias: Alias) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut artist = Self::controlled_artist(&who, artist_id)?;
+            ensure!(artist.name != alias, Error::<T>::AliasIsCanonicalName);
+            let position = artist.aliases.iter().position(|a| a == &alias).ok_or(Error::<T>::AliasNotFound)?;
+
+            artist.aliases.remove(position);
+            AliasOwner::remove(normalize_alias(&alias));
+            <Artists<T>>::insert(artist_id, artist);
+
+            Self::deposit_event(RawEvent::AliasRemoved(artist_id, alias));
+
+            Ok(())
+        }
+
+		/// Move all aliases of `from` to `into` and retire `from`, which keeps resolving
+		/// to `into`. The caller must control both artists.
+		#[weight = 10_000]
+		pub fn merge_artists(origin, into: ArtistId, from: ArtistId) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(into != from, Error::<T>::MergeIntoItself);
+            let mut target = Self::controlled_artist(&who, into)?;
+            let source = Self::controlled_artist(&who, from)?;
+            ensure!(
+                target.aliases.len() + source.aliases.len() <= ARTIST_MAX_ALIASES,
+                Error::<T>::ArtistTooManyAliases
+            );
+
+            for alias in &source.aliases {
+                AliasOwner::insert(normalize_alias(alias), into);
+            }
+            target.aliases.extend(source.aliases);
+            <Artists<T>>::insert(into, target);
+            <Artists<T>>::remove(from);
+            // Artists retired into `from` follow it, so every lookup stays one hop.
+            let mut retired = MergedFrom::take(from);
+            retired.push(from);
+            for artist_id in &retired {
+                MergedInto::insert(artist_id, into);
+            }
+            MergedFrom::mutate(into, |merged| merged.extend(retired));
+
+            Self::deposit_event(RawEvent::ArtistsMerged(from, into));
+
+            Ok(())
+        }
+
+		/// Move `aliases` of an artist to a new artist controlled by `controller`, with the
+		/// first alias as its canonical name, e.g. to hand a wrongly merged artist back to its
+		/// real controller. Only the artist controller may call this.
+		#[weight = 10_000]
+		pub fn split_artist(origin, artist_id: ArtistId, aliases: Vec<Alias>, controller: T::AccountId) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut artist = Self::controlled_artist(&who, artist_id)?;
+            ensure!(!aliases.is_empty(), Error::<T>::AliasNotFound);
+            for (i, alias) in aliases.iter().enumerate() {
+                ensure!(artist.name != *alias, Error::<T>::AliasIsCanonicalName);
+                ensure!(artist.aliases.contains(alias), Error::<T>::AliasNotFound);
+                ensure!(!aliases[..i].contains(alias), Error::<T>::AliasTaken);
+            }
+
+            artist.aliases.retain(|alias| !aliases.contains(alias));
+            let new_artist_id = NextArtistId::get();
+            for alias in &aliases {
+                AliasOwner::insert(normalize_alias(alias), new_artist_id);
+            }
+            let name = aliases[0].clone();
+            <Artists<T>>::insert(new_artist_id, Artist { controller, name, aliases });
+            <Artists<T>>::insert(artist_id, artist);
+            NextArtistId::put(new_artist_id + 1);
+
+            Self::deposit_event(RawEvent::ArtistSplit(artist_id, new_artist_id));
+
+            Ok(())
+        }
+
+		/// Confirm the credit at `index` on `src_id`. Only the linked account may call this.
+		#[weight = 10_000]
+		pub fn confirm_credit(origin, src_id: SrcId, index: u32) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            <SrcCredits<T>>::try_mutate(&src_id, |credits| -> Result<(), Error<T>> {
+                let credit = credits.get_mut(index as usize).ok_or(Error::<T>::CreditNotFound)?;
+                ensure!(credit.account() == Some(&who), Error::<T>::NotCreditedAccount);
+                credit.confirmed = true;
+                Ok(())
+            })?;
+
+            Self::deposit_event(RawEvent::CreditConfirmed(who, src_id, index));
+
+            Ok(())
+        }
+
+		/// Confirm the credit at `index` on a composition. Only the linked account may call this.
+		#[weight = 10_000]
+		pub fn confirm_composition_credit(origin, composition_id: CompositionId, index: u32) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            <CompositionCredits<T>>::try_mutate(&composition_id, |credits| -> Result<(), Error<T>> {
+                let credit = credits.get_mut(index as usize).ok_or(Error::<T>::CreditNotFound)?;
+                ensure!(credit.account() == Some(&who), Error::<T>::NotCreditedAccount);
+                credit.confirmed = true;
+                Ok(())
+            })?;
+
+            Self::deposit_event(RawEvent::CompositionCreditConfirmed(who, composition_id, index));
+
+            Ok(())
+        }
+
+		/// Grant the rights on an SRC to `grantee` for `duration` blocks, after which they
+		/// revert to the caller. Only the current owner may call this; a grantee can
+		/// grant further for at most the remainder of its own term.
+		#[weight = 10_000 + T::OnRightsChange::max_weight()]
+		pub fn grant_rights(origin, src_id: SrcId, grantee: T::AccountId, duration: T::BlockNumber) -> dispatch::DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_src_owner(&who, &src_id)?;
+            ensure!(who != grantee, Error::<T>::GrantToSelf);
+            ensure!(!duration.is_zero(), Error::<T>::GrantDurationZero);
+
+            let start = <frame_system::Module<T>>::block_number();
+            let expires = start.saturating_add(duration);
+            let mut chain = <GrantChain<T>>::get(&src_id);
+            ensure!(chain.len() < GRANT_MAX_CHAIN_LENGTH, Error::<T>::GrantChainTooLong);
+            if let Some(parent) = chain.last() {
+                ensure!(expires <= parent.expires, Error::<T>::GrantExceedsParent);
+            }
+            <GrantExpiries<T>>::try_mutate(expires, |srcs| -> Result<(), Error<T>> {
+                ensure!(srcs.len() < GRANT_MAX_EXPIRIES_PER_BLOCK, Error::<T>::GrantScheduleFull);
+                srcs.push(src_id.clone());
+                Ok(())
+            })?;
+
+            chain.push(Grant { grantor: who.clone(), grantee: grantee.clone(), start, expires });
+            <GrantChain<T>>::insert(&src_id, chain);
+            <GrantedTemplates<T>>::append(&src_id, <LicenseTemplates<T>>::get(&src_id));
+            Self::transfer_ownership(&src_id, &who, &grantee);
+            let hook_weight = T::OnRightsChange::on_granted(&src_id, &who, &grantee);
+
+            Self::deposit_event(RawEvent::RightsGranted(who, src_id, grantee, expires));
+
+            Ok(Some(10_000 + hook_weight).into())
+        }
+
+		/// Replace the licenses offered for an SRC. Only the SRC owner may call this.
+		#[weight = 10_000]
+		pub fn set_license_templates(origin, src_id: SrcId, templates: Vec<LicenseTemplate<BalanceOf<T>, T::BlockNumber>>) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_src_owner(&who, &src_id)?;
+            ensure!(templates.len() <= SRC_MAX_LICENSE_TEMPLATES, Error::<T>::LicenseTemplatesTooMany);
+            for template in &templates {
+                ensure!(!template.term.is_zero(), Error::<T>::LicenseInvalidTerm);
+                ensure!(
+                    template.territories.len() <= TERRITORY_LIST_MAX_LENGTH,
+                    Error::<T>::TerritoryListTooLong
+                );
+                ensure!(
+                    template.territories.iter().all(|code| code.iter().all(u8::is_ascii_uppercase)),
+                    Error::<T>::TerritoryInvalidCode
+                );
+            }
+
+            <LicenseTemplates<T>>::insert(&src_id, templates);
+
+            Self::deposit_event(RawEvent::LicenseTemplatesSet(who, src_id));
+
+            Ok(())
+        }
+
+		/// Buy the license offered as template `index` of an SRC, paying its price to the
+		/// current owner. Fails if the price exceeds `max_price`, protecting the buyer
+		/// from templates replaced in the meantime.
+		#[weight = 10_000]
+		pub fn purchase_license(origin, src_id: SrcId, index: u32, max_price: BalanceOf<T>) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let owner = <OwnerOf<T>>::get(&src_id).ok_or(Error::<T>::SrcNotFound)?;
+            ensure!(!Suspended::get(&src_id), Error::<T>::SrcSuspended);
+            let template = <LicenseTemplates<T>>::get(&src_id)
+                .into_iter()
+                .nth(index as usize)
+                .ok_or(Error::<T>::LicenseTemplateNotFound)?;
+            ensure!(template.price <= max_price, Error::<T>::LicensePriceTooHigh);
+
+            T::Currency::transfer(&who, &owner, template.price, ExistenceRequirement::KeepAlive)?;
+
+            let start = <frame_system::Module<T>>::block_number();
+            let license_id = NextLicenseId::get();
+            let license = License {
+                src_id: src_id.clone(),
+                licensor: owner,
+                licensee: who.clone(),
+                license_type: template.license_type,
+                territories: template.territories,
+                price: template.price,
+                start,
+                expires: start.saturating_add(template.term),
+            };
+            <Licenses<T>>::insert(license_id, license);
+            <LicensesOf<T>>::append(&who, license_id);
+            NextLicenseId::put(license_id + 1);
+
+            Self::deposit_event(RawEvent::LicensePurchased(who, src_id, license_id, template.price));
+
+            Ok(())
+        }
+
+		/// File an infringement notice against an SRC, backed by a hash of the evidence.
+		/// Reserves the takedown bond from the caller and suspends the SRC until the
+		/// notice is resolved.
+		#[weight = 10_000]
+		pub fn file_takedown(origin, src_id: SrcId, evidence: T::Hash) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(<OwnerOf<T>>::contains_key(&src_id), Error::<T>::SrcNotFound);
+            ensure!(!<Takedowns<T>>::contains_key(&src_id), Error::<T>::TakedownPending);
+            ensure!(!Suspended::get(&src_id), Error::<T>::SrcSuspended);
+
+            let now = <frame_system::Module<T>>::block_number();
+            let deadline = now.saturating_add(T::TakedownPeriod::get());
+            ensure!(
+                <TakedownDeadlines<T>>::decode_len(deadline).unwrap_or(0) < TAKEDOWN_MAX_DEADLINES_PER_BLOCK,
+                Error::<T>::TakedownScheduleFull
+            );
+            let bond = T::TakedownBond::get();
+            T::Currency::reserve(&who, bond)?;
+
+            <TakedownDeadlines<T>>::append(deadline, &src_id);
+            <Takedowns<T>>::insert(&src_id, Takedown {
+                claimant: who.clone(),
+                evidence,
+                bond,
+                filed: now,
+                deadline,
+                counter_notice: None,
+            });
+            Suspended::insert(&src_id, true);
+
+            Self::deposit_event(RawEvent::TakedownFiled(who, src_id, evidence));
+
+            Ok(())
+        }
+
+		/// Answer an open takedown notice before its deadline. Only the SRC owner may call
+		/// this; it reserves the takedown bond and restarts the resolution period.
+		#[weight = 10_000]
+		pub fn counter_notice(origin, src_id: SrcId, evidence: T::Hash) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_src_owner(&who, &src_id)?;
+            let mut takedown = <Takedowns<T>>::get(&src_id).ok_or(Error::<T>::TakedownNotFound)?;
+            ensure!(takedown.counter_notice.is_none(), Error::<T>::CounterNoticeExists);
+            let now = <frame_system::Module<T>>::block_number();
+            ensure!(now < takedown.deadline, Error::<T>::TakedownDeadlinePassed);
+
+            let deadline = now.saturating_add(T::TakedownPeriod::get());
+            ensure!(
+                <TakedownDeadlines<T>>::decode_len(deadline).unwrap_or(0) < TAKEDOWN_MAX_DEADLINES_PER_BLOCK,
+                Error::<T>::TakedownScheduleFull
+            );
+            let bond = T::TakedownBond::get();
+            T::Currency::reserve(&who, bond)?;
+
+            Self::unschedule_takedown(&src_id, takedown.deadline);
+            <TakedownDeadlines<T>>::append(deadline, &src_id);
+            takedown.deadline = deadline;
+            takedown.counter_notice = Some(CounterNotice { respondent: who.clone(), evidence, bond });
+            <Takedowns<T>>::insert(&src_id, takedown);
+
+            Self::deposit_event(RawEvent::CounterNoticeFiled(who, src_id, evidence));
+
+            Ok(())
+        }
+
+		/// Resolve an open takedown notice. An upheld notice keeps the SRC suspended and
+		/// forfeits the counter-notice bond to the claimant; a dismissed one reinstates the
+		/// SRC and forfeits the claimant's bond to the owner.
+		#[weight = 10_000 + T::OnTakedownSettled::max_weight()]
+		pub fn resolve_takedown(origin, src_id: SrcId, upheld: bool) -> dispatch::DispatchResultWithPostInfo {
+            T::TakedownOrigin::ensure_origin(origin)?;
+
+            let takedown = <Takedowns<T>>::take(&src_id).ok_or(Error::<T>::TakedownNotFound)?;
+            Self::unschedule_takedown(&src_id, takedown.deadline);
+            let hook_weight = Self::settle_takedown(&src_id, takedown, upheld, true);
+
+            Ok(Some(10_000 + hook_weight).into())
+        }
+
+		/// Lift the suspension left by an upheld takedown notice.
+		#[weight = 10_000]
+		pub fn reinstate_src(origin, src_id: SrcId) -> dispatch::DispatchResult {
+            T::TakedownOrigin::ensure_origin(origin)?;
+
+            ensure!(!<Takedowns<T>>::contains_key(&src_id), Error::<T>::TakedownPending);
+            ensure!(Suspended::get(&src_id), Error::<T>::SrcNotSuspended);
+            Suspended::remove(&src_id);
+
+            Self::deposit_event(RawEvent::SrcReinstated(src_id));
+
+            Ok(())
+        }
+
+		/// Replace the localized titles of an SRC. Only the SRC owner may call this.
+		#[weight = 10_000]
+		pub fn set_src_localizations(origin, src_id: SrcId, localizations: Localizations) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_src_owner(&who, &src_id)?;
+            Self::validate_localizations(&localizations)?;
+
+            SrcLocalizations::insert(&src_id, localizations);
+
+            Self::deposit_event(RawEvent::SrcLocalized(who, src_id));
+
+            Ok(())
+        }
+
+		/// Replace the track list of an album. Only the album owner, who must also own
+		/// every track, may call this.
+		#[weight = 10_000]
+		pub fn set_album_tracks(origin, album_id: AlbumId, tracks: Vec<SrcId>) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_album_owner(&who, &album_id)?;
+            ensure!(tracks.len() <= ALBUM_MAX_TRACKS, Error::<T>::AlbumTooManyTracks);
+            for (i, track) in tracks.iter().enumerate() {
+                ensure!(!tracks[..i].contains(track), Error::<T>::AlbumDuplicateTrack);
+                Self::ensure_src_owner(&who, track)?;
+            }
+
+            AlbumTracks::insert(&album_id, tracks);
+
+            Self::deposit_event(RawEvent::AlbumTracksSet(who, album_id));
+
+            Ok(())
+        }
+
+		/// Replace the localized titles of an album. Only the album owner may call this.
+		#[weight = 10_000]
+		pub fn set_album_localizations(origin, album_id: AlbumId, localizations: Localizations) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_album_owner(&who, &album_id)?;
+            ensure!(!<Embargoes<T>>::contains_key(&album_id), Error::<T>::AlbumEmbargoed);
+            Self::validate_localizations(&localizations)?;
+
+            AlbumLocalizations::insert(&album_id, localizations);
+
+            Self::deposit_event(RawEvent::AlbumLocalized(who, album_id));
+
+            Ok(())
+        }
+	}
+}
+
+impl<T: Config> Module<T> {
+    
+    fn new_song() -> SongBuilder<T::AccountId, T::Moment> {
+        SongBuilder::<T::AccountId, T::Moment>::default()
+    }
+
+    pub fn validate_src_id(src_id: &[u8]) -> Result<(), Error<T>> {
+        // File Hash validation
+        ensure!(!src_id.is_empty(), Error::<T>::SrcIdMissing);
+        ensure!(
+            src_id.len() <= SRC_ID_MAX_LENGTH,
+            Error::<T>::SrcIdTooLong
+        );
+        cid::Cid::parse(src_id).map_err(|err| match err {
+            CidError::Empty => Error::<T>::SrcIdMissing,
+            CidError::Malformed | CidError::DigestLengthMismatch => Error::<T>::SrcIdInvalidCid,
+            CidError::UnsupportedVersion => Error::<T>::SrcIdUnsupportedCidVersion,
+            CidError::UnsupportedCodec => Error::<T>::SrcIdUnsupportedCodec,
+            CidError::UnsupportedHash => Error::<T>::SrcIdUnsupportedHash,
+        })?;
+        Ok(())
+    }
+
+    pub fn validate_song_id(song_id: &[u8]) -> Result<(), Error<T>> {
+        // Basic song ID validation
+        ensure!(!song_id.is_empty(), Error::<T>::SongIdMissing);
+        ensure!(
+            song_id.len() <= SONG_ID_MAX_LENGTH,
+            Error::<T>::SongIdTooLong
+        );
+        Ok(())
+    }
+
+    pub fn validate_new_src_id(src_id: &[u8]) -> Result<(), Error<T>> {
+        // SRC existence check
+        ensure!(
+            !<MusicCollections<T>>::contains_key(src_id),
+            Error::<T>::SrcIdExists
+        );
+        Ok(())
+    }
+
+
+    pub fn ensure_src_owner(who: &T::AccountId, src_id: &[u8]) -> Result<(), Error<T>> {
+        let owner = <OwnerOf<T>>::get(src_id).ok_or(Error::<T>::SrcNotFound)?;
+        ensure!(&owner == who, Error::<T>::NotSrcOwner);
+        Ok(())
+    }
+
+    fn transfer_ownership(src_id: &SrcId, from: &T::AccountId, to: &T::AccountId) {
+        <SrcCollections<T>>::mutate(from, |srcs| srcs.retain(|id| id != src_id));
+        <SrcCollections<T>>::append(to, src_id);
+        <OwnerOf<T>>::insert(src_id, to);
+    }
+
+    /// Pops every grant of `src_id` expiring at or before `now` and hands the
+    /// rights back to its grantor, restoring the license templates offered before
+    /// the grant. Returns the number of reverted grants and the weight of the hooks.
+    fn revert_expired_grants(src_id: &SrcId, now: T::BlockNumber) -> (u64, Weight) {
+        let mut chain = <GrantChain<T>>::get(src_id);
+        let mut templates = <GrantedTemplates<T>>::get(src_id);
+        let mut reverted = 0;
+        let mut weight: Weight = 0;
+        while let Some(grant) = chain.last() {
+            if grant.expires > now {
+                break;
+            }
+            let grant = chain.pop().expect("checked by last() above; qed");
+            match templates.pop() {
+                Some(before) if !before.is_empty() => <LicenseTemplates<T>>::insert(src_id, before),
+                _ => <LicenseTemplates<T>>::remove(src_id),
+            }
+            Self::transfer_ownership(src_id, &grant.grantee, &grant.grantor);
+            weight = weight.saturating_add(T::OnRightsChange::on_reverted(src_id, &grant.grantee, &grant.grantor));
+            Self::deposit_event(RawEvent::RightsReverted(src_id.clone(), grant.grantee, grant.grantor));
+            reverted += 1;
+        }
+        if chain.is_empty() {
+            <GrantChain<T>>::remove(src_id);
+            <GrantedTemplates<T>>::remove(src_id);
+        } else {
+            <GrantChain<T>>::insert(src_id, chain);
+            <GrantedTemplates<T>>::insert(src_id, templates);
+        }
+        (reverted, weight)
+    }
+
+    /// Settles the notice on `src_id` if its deadline is `now`: an unanswered notice is
+    /// upheld, a countered one lapses and the SRC is reinstated. Returns the weight of the
+    /// `OnTakedownSettled` hook if it was settled.
+    fn lapse_takedown(src_id: &SrcId, now: T::BlockNumber) -> Option<Weight> {
+        match <Takedowns<T>>::get(src_id) {
+            Some(takedown) if takedown.deadline == now => {
+                <Takedowns<T>>::remove(src_id);
+                let upheld = takedown.counter_notice.is_none();
+                Some(Self::settle_takedown(src_id, takedown, upheld, false))
+            }
+            _ => None,
+        }
+    }
+
+    /// Drops `src_id` from the notices due to lapse at `deadline`.
+    fn unschedule_takedown(src_id: &SrcId, deadline: T::BlockNumber) {
+        <TakedownDeadlines<T>>::mutate(deadline, |due| due.retain(|id| id != src_id));
+    }
+
+    /// Releases the bonds of a closed notice and updates the suspension of `src_id`.
+    /// Bonds are only forfeited to the other side when the notice was `adjudicated`.
+    /// Returns the weight of the `OnTakedownSettled` hook.
+    fn settle_takedown(
+        src_id: &SrcId,
+        takedown: Takedown<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>,
+        upheld: bool,
+        adjudicated: bool,
+    ) -> Weight {
+        let Takedown { claimant, bond, counter_notice, .. } = takedown;
+        let respondent = counter_notice.as_ref()
+            .map(|counter| counter.respondent.clone())
+            .or_else(|| <OwnerOf<T>>::get(src_id));
+        match respondent {
+            Some(ref respondent) if adjudicated && !upheld => {
+                let _ = T::Currency::repatriate_reserved(&claimant, respondent, bond, BalanceStatus::Free);
+            }
+            _ => {
+                T::Currency::unreserve(&claimant, bond);
+            }
+        }
+        if let Some(counter) = counter_notice {
+            if adjudicated && upheld {
+                let _ = T::Currency::repatriate_reserved(&counter.respondent, &claimant, counter.bond, BalanceStatus::Free);
+            } else {
+                T::Currency::unreserve(&counter.respondent, counter.bond);
+            }
+        }
+
+        if upheld {
+            Self::deposit_event(RawEvent::TakedownUpheld(src_id.clone()));
+        } else {
+            Suspended::remove(src_id);
+            Self::deposit_event(RawEvent::TakedownDismissed(src_id.clone()));
+        }
+        T::OnTakedownSettled::on_settled(src_id, &claimant, upheld)
+    }
+
+    pub fn ensure_album_owner(who: &T::AccountId, album_id: &[u8]) -> Result<(), Error<T>> {
+        let owner = <AlbumOwnerOf<T>>::get(album_id).ok_or(Error::<T>::AlbumNotFound)?;
+        ensure!(&owner == who, Error::<T>::NotAlbumOwner);
+        Ok(())
+    }
+
+    pub fn validate_album_id(album_id: &[u8]) -> Result<(), Error<T>> {
+        ensure!(!album_id.is_empty(), Error::<T>::AlbumIdMissing);
+        ensure!(
+            album_id.len() <= ALBUM_ID_MAX_LENGTH,
+            Error::<T>::AlbumIdTooLong
+        );
+        Ok(())
+    }
+
+    pub fn validate_new_album_id(album_id: &[u8]) -> Result<(), Error<T>> {
+        ensure!(
+            !<Albums<T>>::contains_key(album_id) && !<Embargoes<T>>::contains_key(album_id),
+            Error::<T>::AlbumIdExists
+        );
+        Ok(())
+    }
+
+    /// Validates the barcode and catalog number of an album and returns the
+    /// barcode normalized to 13 digits if no other album uses it yet.
+    pub fn validate_album_codes(album: &Album<T::Moment>) -> Result<[u8; 13], Error<T>> {
+        let gtin = normalize_gtin(album.upc_or_ean()).ok_or(Error::<T>::InvalidGtin)?;
+        ensure!(
+            album.catalog_number().len() <= CATALOG_NUMBER_MAX_LENGTH
+                && album.catalog_number().iter().all(|c| c.is_ascii_graphic() || *c == b' '),
+            Error::<T>::InvalidCatalogNumber
+        );
+        Ok(gtin)
+    }
+
+    /// Album carrying a UPC-A or EAN-13 barcode.
+    pub fn album_by_gtin(gtin: &[u8]) -> Option<AlbumId> {
+        normalize_gtin(gtin).and_then(AlbumByGtin::get)
+    }
+
+    pub fn validate_localizations(localizations: &Localizations) -> Result<(), Error<T>> {
+        let entries = localizations.entries();
+        ensure!(
+            entries.len() <= LOCALIZATION_MAX_ENTRIES,
+            Error::<T>::LocalizationTooManyEntries
+        );
+        for (i, entry) in entries.iter().enumerate() {
+            ensure!(
+                is_language_tag(entry.language()),
+                Error::<T>::LocalizationInvalidLanguageTag
+            );
+            ensure!(
+                !entries[..i].iter().any(|other| other.language().eq_ignore_ascii_case(entry.language())),
+                Error::<T>::LocalizationDuplicateLanguage
+            );
+            ensure!(
+                !entry.title().is_empty() && entry.title().len() <= LOCALIZED_TITLE_MAX_LENGTH,
+                Error::<T>::LocalizationInvalidTitle
+            );
+            ensure!(
+                entry.artist().len() <= LOCALIZED_ARTIST_MAX_LENGTH,
+                Error::<T>::LocalizationInvalidArtist
+            );
+        }
+        ensure!(
+            entries.iter().any(|entry| entry.language() == localizations.default_language()),
+            Error::<T>::LocalizationMissingDefault
+        );
+        Ok(())
+    }
+
+    pub fn validate_territory_rights(rights: &[TerritoryRights<T::Moment>]) -> Result<(), Error<T>> {
+        ensure!(
+            rights.len() <= TERRITORY_RIGHTS_MAX_ENTRIES,
+            Error::<T>::TerritoryRightsTooManyEntries
+        );
+        for right in rights {
+            ensure!(
+                right.include().len() <= TERRITORY_LIST_MAX_LENGTH
+                    && right.exclude().len() <= TERRITORY_LIST_MAX_LENGTH,
+                Error::<T>::TerritoryListTooLong
+            );
+            ensure!(
+                right.include().iter().chain(right.exclude()).all(|code| code.iter().all(u8::is_ascii_uppercase)),
+                Error::<T>::TerritoryInvalidCode
+            );
+            ensure!(
+                right.valid_until().map_or(true, |until| until > right.valid_from()),
+                Error::<T>::TerritoryInvalidWindow
+            );
+        }
+        Ok(())
+    }
+
+    /// Samples of an SRC that are pending or rejected.
+    pub fn uncleared_samples(src_id: &[u8]) -> Vec<Sample> {
+        SrcSamples::get(src_id).into_iter().filter(|sample| !sample.is_cleared()).collect()
+    }
+
+    /// Whether an SRC contains samples that are undeclared or not cleared.
+    pub fn has_uncleared_samples(src_id: &[u8]) -> bool {
+        let samples = SrcSamples::get(src_id);
+        (ContainsSamples::get(src_id) && samples.is_empty())
+            || samples.iter().any(|sample| !sample.is_cleared())
+    }
+
+    /// Royalty shares owed to the masters of cleared samples, as `(sampled_src_id, share_bp)`.
+    /// Suspended sampled SRCs earn nothing while the suspension lasts.
+    pub fn cleared_sample_shares(src_id: &[u8]) -> Vec<(SrcId, u32)> {
+        SrcSamples::get(src_id)
+            .into_iter()
+            .filter(|sample| sample.is_cleared() && !Suspended::get(&sample.src_id))
+            .map(|sample| (sample.src_id, sample.share_bp))
+            .collect()
+    }
+
+    pub fn validate_credits(credits: &[Credit<T::AccountId>]) -> Result<(), Error<T>> {
+        ensure!(credits.len() <= SRC_MAX_CREDITS, Error::<T>::CreditTooMany);
+        for credit in credits {
+            if let Some(party_id) = credit.party() {
+                ensure!(<Parties<T>>::contains_key(party_id), Error::<T>::PartyNotFound);
+            }
+            ensure!(
+                !credit.name().is_empty() && credit.name().len() <= CREDIT_NAME_MAX_LENGTH,
+                Error::<T>::CreditInvalidName
+            );
+            if let ContributorRole::UserDefined(role) = credit.role() {
+                ensure!(
+                    !role.is_empty() && role.len() <= CREDIT_ROLE_MAX_LENGTH,
+                    Error::<T>::CreditInvalidRole
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates the identifiers and indexes them for `party_id`, failing if another
+    /// party already holds one of them.
+    fn claim_identifiers(party_id: PartyId, isni: Option<&Isni>, ipi: Option<&IpiNameNumber>) -> Result<(), Error<T>> {
+        if let Some(isni) = isni {
+            ensure!(is_valid_isni(isni), Error::<T>::InvalidIsni);
+            ensure!(
+                PartyByIsni::get(isni).map_or(true, |holder| holder == party_id),
+                Error::<T>::IsniAlreadyClaimed
+            );
+        }
+        if let Some(ipi) = ipi {
+            ensure!(is_valid_ipi(ipi), Error::<T>::InvalidIpi);
+            ensure!(
+                PartyByIpi::get(ipi).map_or(true, |holder| holder == party_id),
+                Error::<T>::IpiAlreadyClaimed
+            );
+        }
+        if let Some(isni) = isni {
+            PartyByIsni::insert(isni, party_id);
+        }
+        if let Some(ipi) = ipi {
+            PartyByIpi::insert(ipi, party_id);
+        }
+        Ok(())
+    }
+
+    fn insert_artist(controller: T::AccountId, name: ArtistName) -> Result<ArtistId, Error<T>> {
+        let artist_id = NextArtistId::get();
+        Self::claim_alias(artist_id, &name)?;
+
+        let aliases = sp_std::vec![name.clone()];
+        <Artists<T>>::insert(artist_id, Artist { controller, name, aliases });
+        NextArtistId::put(artist_id + 1);
+        Ok(artist_id)
+    }
+
+    fn controlled_artist(who: &T::AccountId, artist_id: ArtistId) -> Result<Artist<T::AccountId>, Error<T>> {
+        let artist = <Artists<T>>::get(artist_id).ok_or(Error::<T>::ArtistNotFound)?;
+        ensure!(&artist.controller == who, Error::<T>::NotArtistController);
+        Ok(artist)
+    }
+
+    fn claim_alias(artist_id: ArtistId, alias: &[u8]) -> Result<(), Error<T>> {
+        ensure!(
+            !alias.is_empty() && alias.len() <= ARTIST_ALIAS_MAX_LENGTH,
+            Error::<T>::AliasInvalid
+        );
+        let key = normalize_alias(alias);
+        ensure!(!AliasOwner::contains_key(&key), Error::<T>::AliasTaken);
+        AliasOwner::insert(key, artist_id);
+        Ok(())
+    }
+
+    /// Current id of an artist, following merges.
+    pub fn canonical_artist_id(artist_id: ArtistId) -> ArtistId {
+        MergedInto::get(artist_id).unwrap_or(artist_id)
+    }
+
+    /// Artist owning `name` as one of its aliases.
+    pub fn resolve_alias(name: &[u8]) -> Option<ArtistId> {
+        AliasOwner::get(normalize_alias(name)).map(Self::canonical_artist_id)
+    }
+
+    /// Artists credited in the props of an SRC, resolved through their aliases.
+    pub fn artists_of_src(src_id: &[u8]) -> Vec<ArtistId> {
+        let mut artists: Vec<ArtistId> = Vec::new();
+        let props = <MusicCollections<T>>::get(src_id).and_then(|music| music.props).unwrap_or_default();
+        for prop in props {
+            if let Some(artist_id) = Self::resolve_alias(prop.artist()) {
+                if !artists.contains(&artist_id) {
+                    artists.push(artist_id);
+                }
+            }
+        }
+        artists
+    }
+
+    /// Accounts that confirmed a credit on an SRC, each listed once.
+    pub fn confirmed_contributors(src_id: &[u8]) -> Vec<T::AccountId> {
+        let mut accounts: Vec<T::AccountId> = Vec::new();
+        for credit in <SrcCredits<T>>::get(src_id) {
+            if let (true, Some(account)) = (credit.confirmed, credit.account) {
+                if !accounts.contains(&account) {
+                    accounts.push(account);
+                }
+            }
+        }
+        accounts
+    }
+
+    /// Owner of a registered SRC or composition.
+    pub fn work_owner(work: &Work) -> Result<T::AccountId, Error<T>> {
+        match work {
+            Work::Src(src_id) => <OwnerOf<T>>::get(src_id).ok_or(Error::<T>::SrcNotFound),
+            Work::Composition(composition_id) => {
+                <CompositionOwnerOf<T>>::get(composition_id).ok_or(Error::<T>::CompositionNotFound)
+            }
+        }
+    }
+
+    /// Whether `work` derives from the SRC `src_id`, directly or through other derivatives.
+    /// Fails when `work` has more than `DERIVATIVES_QUERY_MAX_RESULTS` ancestors.
+    pub fn derives_from(work: &Work, src_id: &[u8]) -> Result<bool, Error<T>> {
+        let mut visited: Vec<SrcId> = Vec::new();
+        let mut next = 0;
+        let mut current = match work {
+            Work::Src(work_src_id) => work_src_id.clone(),
+            Work::Composition(_) => return Ok(false),
+        };
+        loop {
+            for derivation in DerivedFrom::get(&current) {
+                if let Work::Src(original) = derivation.original {
+                    if original == src_id {
+                        return Ok(true);
+                    }
+                    if !visited.contains(&original) {
+                        ensure!(visited.len() < DERIVATIVES_QUERY_MAX_RESULTS, Error::<T>::DerivationGraphTooLarge);
+                        visited.push(original);
+                    }
+                }
+            }
+            match visited.get(next) {
+                Some(original) => current = original.clone(),
+                None => return Ok(false),
+            }
+            next += 1;
+        }
+    }
+
+    /// All SRCs derived from `work`, directly or through other derivatives,
+    /// in breadth-first order. At most `DERIVATIVES_QUERY_MAX_RESULTS` are returned.
+    pub fn all_derivatives(work: &Work) -> Vec<SrcId> {
+        let mut found: Vec<SrcId> = Vec::new();
+        let mut next = 0;
+        let mut frontier = DerivativesOf::get(work);
+        loop {
+            for src_id in frontier {
+                if found.len() >= DERIVATIVES_QUERY_MAX_RESULTS {
+                    return found;
+                }
+                if !found.contains(&src_id) {
+                    found.push(src_id);
+                }
+            }
+            match found.get(next) {
+                Some(src_id) => frontier = DerivativesOf::get(Work::Src(src_id.clone())),
+                None => return found,
+            }
+            next += 1;
+        }
+    }
+
+    pub fn validate_genre(genre_id: GenreId, genre: &Genre) -> Result<(), Error<T>> {
+        ensure!(genre_id != GENRE_UNSPECIFIED, Error::<T>::GenreInvalidId);
+        ensure!(
+            !genre.name().is_empty() && genre.name().len() <= GENRE_NAME_MAX_LENGTH,
+            Error::<T>::GenreInvalidName
+        );
+        // Walk up from the new parent; reaching `genre_id` would close a cycle.
+        let mut ancestors = 0;
+        let mut ancestor = genre.parent();
+        while let Some(id) = ancestor {
+            ensure!(id != genre_id, Error::<T>::GenreCycle);
+            ancestors += 1;
+            ensure!(ancestors < GENRE_MAX_DEPTH, Error::<T>::GenreTooDeep);
+            ancestor = Genres::get(id).ok_or(Error::<T>::GenreParentNotFound)?.parent();
+        }
+        // A re-parented genre moves its whole subtree along with it.
+        ensure!(
+            ancestors + Self::genre_height(genre_id) <= GENRE_MAX_DEPTH,
+            Error::<T>::GenreTooDeep
+        );
+        Ok(())
+    }
+
+    /// Levels in the subtree rooted at `genre_id`, counting the genre itself.
+    fn genre_height(genre_id: GenreId) -> usize {
+        let mut height = 0;
+        let mut level = vec![genre_id];
+        while !level.is_empty() && height <= GENRE_MAX_DEPTH {
+            height += 1;
+            level = level.into_iter().flat_map(|id| GenreChildren::get(id)).collect();
+        }
+        height
+    }
+
+    pub fn validate_album_genre(genre_id: GenreId) -> Result<(), Error<T>> {
+        ensure!(
+            genre_id == GENRE_UNSPECIFIED || Genres::contains_key(genre_id),
+            Error::<T>::GenreNotFound
+        );
+        Ok(())
+    }
+
+    /// Whether license `license_id` lets `licensee` use `src_id` for `license_type`
+    /// in `territory` at the current block.
+    pub fn verify_license(
+        license_id: LicenseId,
+        licensee: &T::AccountId,
+        src_id: &[u8],
+        license_type: LicenseType,
+        territory: &Territory,
+    ) -> bool {
+        if Suspended::get(src_id) {
+            return false;
+        }
+        let now = <frame_system::Module<T>>::block_number();
+        <Licenses<T>>::get(license_id)
+            .map_or(false, |license| license.permits(licensee, src_id, license_type, territory, &now))
+    }
+
+    /// Whether the SRC may be exploited in `territory` at `time`.
+    ///
+    /// SRCs without declared territory rights are available worldwide; unregistered
+    /// and suspended SRCs are available nowhere.
+    pub fn is_available_in(src_id: &[u8], territory: &Territory, time: T::Moment) -> bool {
+        if !<OwnerOf<T>>::contains_key(src_id) || Suspended::get(src_id) {
+            return false;
+        }
+        let rights = <SrcTerritoryRights<T>>::get(src_id);
+        rights.is_empty() || rights.iter().any(|right| right.covers(territory, &time))
+    }
+
+    pub fn validate_song_props(props: &Option<Vec<TestData>>) -> Result<(), Error<T>> {
+        if let Some(props) = props {
+            ensure!(
+                props.len() <= SONG_MAX_PROPS,
+                Error::<T>::SongTooManyProps,
+            );
+            for prop in props {
+                ensure!(
+                    prop.name().len() <= SONG_NAME_MAX_LENGTH,
+                    Error::<T>::SongInvalidSongName
+                );
+                ensure!(
+                    prop.artist().len() <= ARTIST_NAME_MAX_LENGTH,
+                    Error::<T>::SongInvalidArtistName
+                );
 				ensure!(
                     prop.composer().len() <= COMPOSER_MAX_LENGTH,
                     Error::<T>::SongInvalidComposer
@@ -317,6 +2528,101 @@ impl<T: Config> Module<T> {
     }
 }
 
+/// Checks the syntax of a BCP-47 language tag: alphanumeric subtags of 1 to 8
+/// characters separated by hyphens, starting with a 2-3 or 5-8 letter language
+/// subtag (or the `x`/`i` singleton of private use and grandfathered tags).
+/// Registry membership of the individual subtags is not checked on-chain.
+pub fn is_language_tag(tag: &[u8]) -> bool {
+    if tag.is_empty() || tag.len() > LANGUAGE_TAG_MAX_LENGTH {
+        return false;
+    }
+    let mut subtags = tag.split(|c| *c == b'-');
+    let primary = match subtags.next() {
+        Some(primary) => primary,
+        None => return false,
+    };
+    let singleton = primary.len() == 1 && matches!(primary[0].to_ascii_lowercase(), b'x' | b'i');
+    if !singleton && !matches!(primary.len(), 2..=3 | 5..=8) {
+        return false;
+    }
+    if !primary.iter().all(u8::is_ascii_alphabetic) {
+        return false;
+    }
+    subtags.all(|subtag| {
+        !subtag.is_empty() && subtag.len() <= 8 && subtag.iter().all(u8::is_ascii_alphanumeric)
+    })
+}
+
+/// Key under which an alias is claimed: ASCII case and surrounding or repeated
+/// whitespace are ignored, and an inverted article (`Band, The`) is moved to the
+/// front, so that `The Band` and `Band, The` are the same alias.
+pub fn normalize_alias(alias: &[u8]) -> Vec<u8> {
+    let mut words: Vec<Vec<u8>> = alias
+        .split(u8::is_ascii_whitespace)
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_ascii_lowercase())
+        .collect();
+    let inverted = words.len() > 1
+        && words.last().map_or(false, |last| matches!(&last[..], b"the" | b"a" | b"an"))
+        && words[words.len() - 2].last() == Some(&b',');
+    if inverted {
+        let article = words.pop().unwrap_or_default();
+        if let Some(word) = words.last_mut() {
+            word.pop();
+        }
+        words.insert(0, article);
+    }
+    words.join(&b' ')
+}
+
+/// Checks the length and check digit of a GTIN-12 or GTIN-13 and returns it as
+/// GTIN-13, so a UPC-A and its EAN-13 form (with a leading zero) are the same key.
+pub fn normalize_gtin(gtin: &[u8]) -> Option<[u8; 13]> {
+    if !matches!(gtin.len(), 12 | 13) || !gtin.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    let mut normalized = [b'0'; 13];
+    normalized[13 - gtin.len()..].copy_from_slice(gtin);
+    let sum = normalized[..12]
+        .iter()
+        .enumerate()
+        .fold(0u32, |sum, (i, digit)| sum + u32::from(digit - b'0') * if i % 2 == 0 { 1 } else { 3 });
+    if (10 - sum % 10) % 10 == u32::from(normalized[12] - b'0') {
+        Some(normalized)
+    } else {
+        None
+    }
+}
+
+/// Checks the ISO 7064 MOD 11-2 check character of an ISNI.
+pub fn is_valid_isni(isni: &Isni) -> bool {
+    if !isni[..15].iter().all(u8::is_ascii_digit) {
+        return false;
+    }
+    let total = isni[..15]
+        .iter()
+        .fold(0u32, |total, digit| ((total + u32::from(digit - b'0')) * 2) % 11);
+    let check = (12 - total) % 11;
+    match check {
+        10 => isni[15] == b'X',
+        digit => isni[15] == b'0' + digit as u8,
+    }
+}
+
+/// Checks the modulus 101 check digits of an IPI name number: the first nine
+/// digits weighted 10 down to 2, summed, modulo 101.
+pub fn is_valid_ipi(ipi: &IpiNameNumber) -> bool {
+    if !ipi.iter().all(u8::is_ascii_digit) {
+        return false;
+    }
+    let sum = ipi[..9]
+        .iter()
+        .zip((2..=10u32).rev())
+        .fold(0u32, |sum, (digit, weight)| sum + u32::from(digit - b'0') * weight);
+    let check = u32::from(ipi[9] - b'0') * 10 + u32::from(ipi[10] - b'0');
+    sum % 101 % 100 == check
+}
+
 #[derive(Default)]
 pub struct SongBuilder<AccountId, Moment>
 where
@@ -0,0 +1,752 @@
+use crate::{
+	cid, Album, Comp, ContributorRole, Credit, DerivativeKind, Error, Genre, Localization, Localizations,
+	LicenseTemplate, LicenseType, PartyKind, TerritoryRights, TestData, Work, is_valid_ipi, is_valid_isni,
+	normalize_alias, mock::*,
+};
+use frame_support::{assert_ok, assert_noop, dispatch::DispatchError, traits::OnInitialize};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+/// Binary CID of an audio file whose content is `label`.
+fn src(label: &[u8]) -> Vec<u8> {
+	cid::cid_v1(cid::CODEC_RAW, cid::SHA2_256, &sp_io::hashing::sha2_256(label))
+}
+
+fn register(owner: u64, src_id: &[u8]) {
+	assert_ok!(RightsMgmtPortal::register_music(
+		Origin::signed(owner),
+		src_id.to_vec(),
+		b"USRC17607839".to_vec(),
+		owner,
+		None,
+	));
+}
+
+#[test]
+fn registers_music_with_owner() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		assert_eq!(RightsMgmtPortal::owner_of(src(b"src-1")), Some(1));
+		assert_eq!(RightsMgmtPortal::products_of_org(1), vec![src(b"src-1")]);
+		assert_noop!(
+			RightsMgmtPortal::register_music(Origin::signed(2), src(b"src-1"), b"isrc".to_vec(), 2, None),
+			Error::<Test>::SrcIdExists
+		);
+	});
+}
+
+#[test]
+fn src_ids_must_be_supported_cids() {
+	new_test_ext().execute_with(|| {
+		let register_as = |src_id: Vec<u8>| {
+			RightsMgmtPortal::register_music(Origin::signed(1), src_id, b"isrc".to_vec(), 1, None)
+		};
+		let digest = sp_io::hashing::sha2_256(b"audio");
+		assert_noop!(register_as(b"src-1".to_vec()), Error::<Test>::SrcIdUnsupportedCidVersion);
+		// CIDv0, a bare sha2-256 multihash.
+		let mut v0 = vec![0x12, 0x20];
+		v0.extend_from_slice(&digest);
+		assert_noop!(register_as(v0), Error::<Test>::SrcIdUnsupportedCidVersion);
+		assert_noop!(
+			register_as(cid::cid_v1(0x71, cid::SHA2_256, &digest)),
+			Error::<Test>::SrcIdUnsupportedCodec
+		);
+		assert_noop!(
+			register_as(cid::cid_v1(cid::CODEC_RAW, 0x11, &digest[..20])),
+			Error::<Test>::SrcIdUnsupportedHash
+		);
+		assert_noop!(
+			register_as(cid::cid_v1(cid::CODEC_RAW, cid::SHA2_256, &digest[..31])),
+			Error::<Test>::SrcIdInvalidCid
+		);
+
+		assert_ok!(register_as(cid::cid_v1(cid::CODEC_RAW, cid::SHA2_256, &digest)));
+		let blake2b = cid::cid_v1(cid::CODEC_DAG_PB, cid::BLAKE2B_512, &[7; 64]);
+		assert_eq!(blake2b.len(), crate::SRC_ID_MAX_LENGTH);
+		assert_ok!(register_as(blake2b));
+	});
+}
+
+#[test]
+fn parses_cids() {
+	let digest = [1; 32];
+	let bytes = cid::cid_v1(cid::CODEC_RAW, cid::BLAKE2B_256, &digest);
+	assert_eq!(&bytes[..6], &[0x01, 0x55, 0xa0, 0xe4, 0x02, 0x20]);
+	let parsed = cid::Cid::parse(&bytes).unwrap();
+	assert_eq!(parsed.codec(), cid::CODEC_RAW);
+	assert_eq!(parsed.hash(), cid::BLAKE2B_256);
+	assert_eq!(parsed.digest(), &digest);
+
+	assert_eq!(cid::Cid::parse(&[]), Err(cid::CidError::Empty));
+	assert_eq!(cid::Cid::parse(&[0x81]), Err(cid::CidError::Malformed));
+	// Non-minimal varint for version 1.
+	assert_eq!(cid::Cid::parse(&[0x81, 0x00, 0x55]), Err(cid::CidError::Malformed));
+	let mut trailing = cid::cid_v1(cid::CODEC_RAW, cid::SHA2_256, &digest);
+	trailing.push(0);
+	assert_eq!(cid::Cid::parse(&trailing), Err(cid::CidError::DigestLengthMismatch));
+}
+
+#[test]
+fn owner_sets_src_localizations() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		let localizations = Localizations::new(b"ja", vec![
+			Localization::new(b"ja", "夜に駆ける".as_bytes(), "YOASOBI".as_bytes()),
+			Localization::new(b"ja-Latn", b"Yoru ni Kakeru", b"YOASOBI"),
+		]);
+		assert_ok!(RightsMgmtPortal::set_src_localizations(Origin::signed(1), src(b"src-1"), localizations.clone()));
+
+		let stored = RightsMgmtPortal::src_localizations(src(b"src-1")).unwrap();
+		assert_eq!(stored, localizations);
+		assert_eq!(stored.get(b"ja-Latn").unwrap().title(), b"Yoru ni Kakeru");
+		assert_eq!(stored.get(b"fr").unwrap().language(), b"ja");
+	});
+}
+
+#[test]
+fn rejects_invalid_localizations() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		let missing_default = Localizations::new(b"en", vec![Localization::new(b"de", b"Titel", b"")]);
+		assert_noop!(
+			RightsMgmtPortal::set_src_localizations(Origin::signed(1), src(b"src-1"), missing_default),
+			Error::<Test>::LocalizationMissingDefault
+		);
+		let bad_tag = Localizations::new(b"en_US", vec![Localization::new(b"en_US", b"Title", b"")]);
+		assert_noop!(
+			RightsMgmtPortal::set_src_localizations(Origin::signed(1), src(b"src-1"), bad_tag),
+			Error::<Test>::LocalizationInvalidLanguageTag
+		);
+		let duplicate = Localizations::new(b"en", vec![
+			Localization::new(b"en", b"Title", b""),
+			Localization::new(b"EN", b"Title", b""),
+		]);
+		assert_noop!(
+			RightsMgmtPortal::set_src_localizations(Origin::signed(1), src(b"src-1"), duplicate),
+			Error::<Test>::LocalizationDuplicateLanguage
+		);
+		let valid = Localizations::new(b"en", vec![Localization::new(b"en", b"Title", b"")]);
+		assert_noop!(
+			RightsMgmtPortal::set_src_localizations(Origin::signed(2), src(b"src-1"), valid),
+			Error::<Test>::NotSrcOwner
+		);
+	});
+}
+
+fn album_with_gtin(release_date: u64, gtin: &[u8]) -> Album<u64> {
+	Album {
+		album_artist: b"Artist".to_vec(),
+		album_producer: vec![],
+		album_title: "Unreleased".encode_utf16().collect(),
+		album_type: vec![],
+		c_line: vec![],
+		country_of_origin: b"US".to_vec(),
+		display_label_name: vec![],
+		explicit_: false,
+		genre_1: 0,
+		master_label_name: vec![],
+		p_line: vec![],
+		part_of_album: false,
+		release_date,
+		sales_start_date: vec![],
+		upc_or_ean: gtin.to_vec(),
+		catalog_number: b"PM-001".to_vec(),
+	}
+}
+
+fn album(release_date: u64) -> Album<u64> {
+	album_with_gtin(release_date, b"036000291452")
+}
+
+#[test]
+fn embargoed_album_is_revealed_after_release_date() {
+	new_test_ext().execute_with(|| {
+		let album = album(1_000);
+		let salt = b"salt".to_vec();
+		let hash = BlakeTwo256::hash_of(&(&album, &salt));
+		assert_noop!(
+			RightsMgmtPortal::register_embargoed_album(Origin::signed(1), b"album-1".to_vec(), 1, b"036000291453".to_vec(), hash, 1_000),
+			Error::<Test>::InvalidGtin
+		);
+		assert_ok!(RightsMgmtPortal::register_embargoed_album(Origin::signed(1), b"album-1".to_vec(), 1, b"036000291452".to_vec(), hash, 1_000));
+		assert_eq!(RightsMgmtPortal::album_by_id(b"album-1".to_vec()), None);
+		// The barcode is reserved during the embargo.
+		assert_noop!(
+			RightsMgmtPortal::register_album(Origin::signed(2), b"album-2".to_vec(), 2, album_with_gtin(0, b"0036000291452")),
+			Error::<Test>::GtinExists
+		);
+
+		assert_noop!(
+			RightsMgmtPortal::reveal(Origin::signed(1), b"album-1".to_vec(), album.clone(), salt.clone()),
+			Error::<Test>::EmbargoNotExpired
+		);
+
+		Timestamp::set_timestamp(1_000);
+		assert_noop!(
+			RightsMgmtPortal::reveal(Origin::signed(1), b"album-1".to_vec(), album.clone(), b"other".to_vec()),
+			Error::<Test>::EmbargoHashMismatch
+		);
+		assert_noop!(
+			RightsMgmtPortal::reveal(Origin::signed(2), b"album-1".to_vec(), album.clone(), salt.clone()),
+			Error::<Test>::NotAlbumOwner
+		);
+		assert_ok!(RightsMgmtPortal::reveal(Origin::signed(1), b"album-1".to_vec(), album.clone(), salt));
+		assert_eq!(RightsMgmtPortal::album_by_id(b"album-1".to_vec()), Some(album));
+		assert_eq!(RightsMgmtPortal::embargo_of(b"album-1".to_vec()), None);
+	});
+}
+
+#[test]
+fn territory_rights_restrict_availability() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		assert!(RightsMgmtPortal::is_available_in(&src(b"src-1"), b"JP", 0));
+		assert!(!RightsMgmtPortal::is_available_in(&src(b"src-2"), b"JP", 0));
+
+		let rights = vec![
+			TerritoryRights::new(vec![], vec![*b"US", *b"CA"], 100, Some(200)),
+			TerritoryRights::new(vec![*b"US"], vec![], 150, None),
+		];
+		assert_ok!(RightsMgmtPortal::set_territory_rights(Origin::signed(1), src(b"src-1"), rights));
+
+		assert!(!RightsMgmtPortal::is_available_in(&src(b"src-1"), b"DE", 99));
+		assert!(RightsMgmtPortal::is_available_in(&src(b"src-1"), b"DE", 100));
+		assert!(!RightsMgmtPortal::is_available_in(&src(b"src-1"), b"DE", 200));
+		assert!(!RightsMgmtPortal::is_available_in(&src(b"src-1"), b"CA", 120));
+		assert!(!RightsMgmtPortal::is_available_in(&src(b"src-1"), b"US", 120));
+		assert!(RightsMgmtPortal::is_available_in(&src(b"src-1"), b"US", 1_000));
+
+		assert_noop!(
+			RightsMgmtPortal::set_territory_rights(Origin::signed(1), src(b"src-1"), vec![
+				TerritoryRights::new(vec![*b"us"], vec![], 0, None),
+			]),
+			Error::<Test>::TerritoryInvalidCode
+		);
+		assert_noop!(
+			RightsMgmtPortal::set_territory_rights(Origin::signed(1), src(b"src-1"), vec![
+				TerritoryRights::new(vec![], vec![], 10, Some(10)),
+			]),
+			Error::<Test>::TerritoryInvalidWindow
+		);
+	});
+}
+
+#[test]
+fn genre_taxonomy_is_governed_and_indexed() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			RightsMgmtPortal::set_genre(Origin::signed(1), 1, Genre::new(None, b"Jazz")),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(RightsMgmtPortal::set_genre(Origin::root(), 1, Genre::new(None, b"Jazz")));
+		assert_ok!(RightsMgmtPortal::set_genre(Origin::root(), 2, Genre::new(Some(1), b"Bebop")));
+		assert_noop!(
+			RightsMgmtPortal::set_genre(Origin::root(), 3, Genre::new(Some(9), b"Swing")),
+			Error::<Test>::GenreParentNotFound
+		);
+		assert_noop!(
+			RightsMgmtPortal::set_genre(Origin::root(), 1, Genre::new(Some(2), b"Jazz")),
+			Error::<Test>::GenreCycle
+		);
+
+		register(1, &src(b"src-1"));
+		register(1, &src(b"src-2"));
+		assert_noop!(
+			RightsMgmtPortal::set_src_genres(Origin::signed(1), src(b"src-1"), vec![7]),
+			Error::<Test>::GenreNotFound
+		);
+		assert_ok!(RightsMgmtPortal::set_src_genres(Origin::signed(1), src(b"src-1"), vec![1, 2]));
+		assert_ok!(RightsMgmtPortal::set_src_genres(Origin::signed(1), src(b"src-2"), vec![2]));
+		assert_eq!(RightsMgmtPortal::srcs_by_genre(2), vec![src(b"src-1"), src(b"src-2")]);
+
+		assert_ok!(RightsMgmtPortal::set_src_genres(Origin::signed(1), src(b"src-1"), vec![1]));
+		assert_eq!(RightsMgmtPortal::srcs_by_genre(1), vec![src(b"src-1")]);
+		assert_eq!(RightsMgmtPortal::srcs_by_genre(2), vec![src(b"src-2")]);
+
+		let mut unknown_genre = album(0);
+		unknown_genre.genre_1 = 5;
+		assert_noop!(
+			RightsMgmtPortal::register_album(Origin::signed(1), b"album-1".to_vec(), 1, unknown_genre),
+			Error::<Test>::GenreNotFound
+		);
+	});
+}
+
+#[test]
+fn re_parented_genres_keep_their_subtree_within_max_depth() {
+	new_test_ext().execute_with(|| {
+		// A chain of GENRE_MAX_DEPTH - 1 levels: 10 -> 11 -> ... -> 16.
+		assert_ok!(RightsMgmtPortal::set_genre(Origin::root(), 10, Genre::new(None, b"Rock")));
+		for id in 11..17 {
+			assert_ok!(RightsMgmtPortal::set_genre(Origin::root(), id, Genre::new(Some(id - 1), b"Rock")));
+		}
+		assert_ok!(RightsMgmtPortal::set_genre(Origin::root(), 20, Genre::new(None, b"Funk")));
+		assert_ok!(RightsMgmtPortal::set_genre(Origin::root(), 21, Genre::new(Some(20), b"P-Funk")));
+		assert_eq!(RightsMgmtPortal::genre_children(20), vec![21]);
+
+		// Genre 20 fits under 16 on its own, but its child would not.
+		assert_noop!(
+			RightsMgmtPortal::set_genre(Origin::root(), 20, Genre::new(Some(16), b"Funk")),
+			Error::<Test>::GenreTooDeep
+		);
+		assert_ok!(RightsMgmtPortal::set_genre(Origin::root(), 20, Genre::new(Some(15), b"Funk")));
+		assert_eq!(RightsMgmtPortal::genre_children(15), vec![16, 20]);
+		assert_noop!(
+			RightsMgmtPortal::set_genre(Origin::root(), 22, Genre::new(Some(21), b"G-Funk")),
+			Error::<Test>::GenreTooDeep
+		);
+
+		assert_ok!(RightsMgmtPortal::set_genre(Origin::root(), 20, Genre::new(None, b"Funk")));
+		assert_eq!(RightsMgmtPortal::genre_children(15), vec![16]);
+		assert_ok!(RightsMgmtPortal::set_genre(Origin::root(), 22, Genre::new(Some(21), b"G-Funk")));
+	});
+}
+
+#[test]
+fn samples_are_cleared_by_sampled_owner() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		register(2, &src(b"src-2"));
+		assert!(!RightsMgmtPortal::has_uncleared_samples(&src(b"src-1")));
+		assert_ok!(RightsMgmtPortal::set_contains_samples(Origin::signed(1), src(b"src-1"), true));
+		assert!(RightsMgmtPortal::has_uncleared_samples(&src(b"src-1")));
+
+		assert_noop!(
+			RightsMgmtPortal::declare_sample(Origin::signed(1), src(b"src-1"), src(b"src-3"), 500),
+			Error::<Test>::SrcNotFound
+		);
+		assert_ok!(RightsMgmtPortal::declare_sample(Origin::signed(1), src(b"src-1"), src(b"src-2"), 500));
+		assert_eq!(RightsMgmtPortal::sampled_by(src(b"src-2")), vec![src(b"src-1")]);
+		assert_eq!(RightsMgmtPortal::uncleared_samples(&src(b"src-1")).len(), 1);
+		assert!(RightsMgmtPortal::cleared_sample_shares(&src(b"src-1")).is_empty());
+
+		assert_noop!(
+			RightsMgmtPortal::respond_to_sample(Origin::signed(1), src(b"src-1"), src(b"src-2"), true),
+			Error::<Test>::NotSrcOwner
+		);
+		assert_ok!(RightsMgmtPortal::respond_to_sample(Origin::signed(2), src(b"src-1"), src(b"src-2"), true));
+		assert!(!RightsMgmtPortal::has_uncleared_samples(&src(b"src-1")));
+		assert_eq!(RightsMgmtPortal::cleared_sample_shares(&src(b"src-1")), vec![(src(b"src-2"), 500)]);
+
+		// A clearance cannot be withdrawn once royalties are split along it.
+		assert_noop!(
+			RightsMgmtPortal::respond_to_sample(Origin::signed(2), src(b"src-1"), src(b"src-2"), false),
+			Error::<Test>::SampleAlreadyAnswered
+		);
+		assert!(!RightsMgmtPortal::has_uncleared_samples(&src(b"src-1")));
+	});
+}
+
+#[test]
+fn derivatives_form_a_queryable_graph() {
+	new_test_ext().execute_with(|| {
+		let comp = Comp {
+			pro: vec![],
+			composition_title: "Song".encode_utf16().collect(),
+			publishers: vec![],
+			third_party_publishers: false,
+			writers: vec![],
+			created: 0,
+		};
+		assert_ok!(RightsMgmtPortal::register_composition(Origin::signed(9), b"T-034.524.680-1".to_vec(), 9, comp));
+		let composition = Work::Composition(b"T-034.524.680-1".to_vec());
+		register(1, &src(b"original"));
+		register(2, &src(b"cover"));
+		register(3, &src(b"remix"));
+
+		assert_ok!(RightsMgmtPortal::declare_derivative(Origin::signed(1), src(b"original"), composition.clone(), DerivativeKind::Recording));
+		assert_ok!(RightsMgmtPortal::declare_derivative(Origin::signed(2), src(b"cover"), composition.clone(), DerivativeKind::Cover));
+		assert_ok!(RightsMgmtPortal::declare_derivative(Origin::signed(3), src(b"remix"), Work::Src(src(b"cover")), DerivativeKind::Remix));
+		assert_noop!(
+			RightsMgmtPortal::declare_derivative(Origin::signed(3), src(b"remix"), Work::Src(src(b"remix")), DerivativeKind::Remix),
+			Error::<Test>::DerivativeOfItself
+		);
+		// original <- cover <- remix: the original may not derive from the remix.
+		assert_ok!(RightsMgmtPortal::declare_derivative(Origin::signed(2), src(b"cover"), Work::Src(src(b"original")), DerivativeKind::Cover));
+		assert_noop!(
+			RightsMgmtPortal::declare_derivative(Origin::signed(1), src(b"original"), Work::Src(src(b"remix")), DerivativeKind::RadioEdit),
+			Error::<Test>::DerivationCycle
+		);
+
+		assert_eq!(
+			RightsMgmtPortal::all_derivatives(&composition),
+			vec![src(b"original"), src(b"cover"), src(b"remix")]
+		);
+
+		assert_noop!(
+			RightsMgmtPortal::acknowledge_derivative(Origin::signed(1), src(b"remix"), Work::Src(src(b"cover"))),
+			Error::<Test>::NotWorkOwner
+		);
+		assert_ok!(RightsMgmtPortal::acknowledge_derivative(Origin::signed(2), src(b"remix"), Work::Src(src(b"cover"))));
+		assert!(RightsMgmtPortal::derived_from(src(b"remix"))[0].is_acknowledged());
+	});
+}
+
+#[test]
+fn credits_are_confirmed_by_contributors() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		let credits = vec![
+			Credit::new(ContributorRole::Producer, b"Producer", Some(2)),
+			Credit::new(ContributorRole::MixingEngineer, b"Engineer", Some(3)),
+			Credit::new(ContributorRole::UserDefined(b"Theremin".to_vec()), b"Player", None),
+		];
+		assert_ok!(RightsMgmtPortal::set_credits(Origin::signed(1), src(b"src-1"), credits.clone()));
+		assert_eq!(RightsMgmtPortal::credited_srcs(2), vec![src(b"src-1")]);
+
+		assert_noop!(
+			RightsMgmtPortal::confirm_credit(Origin::signed(3), src(b"src-1"), 0),
+			Error::<Test>::NotCreditedAccount
+		);
+		assert_ok!(RightsMgmtPortal::confirm_credit(Origin::signed(2), src(b"src-1"), 0));
+		assert_eq!(RightsMgmtPortal::confirmed_contributors(&src(b"src-1")), vec![2]);
+
+		// Unchanged credits keep their confirmation, removed ones leave the index.
+		assert_ok!(RightsMgmtPortal::set_credits(Origin::signed(1), src(b"src-1"), credits[..1].to_vec()));
+		assert_eq!(RightsMgmtPortal::confirmed_contributors(&src(b"src-1")), vec![2]);
+		assert!(RightsMgmtPortal::credited_srcs(3).is_empty());
+	});
+}
+
+#[test]
+fn composition_credits_are_confirmed_by_contributors() {
+	new_test_ext().execute_with(|| {
+		let comp = Comp {
+			pro: vec![],
+			composition_title: "Song".encode_utf16().collect(),
+			publishers: vec![],
+			third_party_publishers: false,
+			writers: vec![],
+			created: 0,
+		};
+		assert_ok!(RightsMgmtPortal::register_composition(Origin::signed(9), b"T-034.524.680-1".to_vec(), 9, comp));
+		let credits = vec![
+			Credit::new(ContributorRole::Composer, b"Writer", Some(2)),
+			Credit::new(ContributorRole::Lyricist, b"Lyricist", Some(3)),
+		];
+		assert_ok!(RightsMgmtPortal::set_composition_credits(Origin::signed(9), b"T-034.524.680-1".to_vec(), credits.clone()));
+
+		assert_noop!(
+			RightsMgmtPortal::confirm_composition_credit(Origin::signed(3), b"T-034.524.680-1".to_vec(), 0),
+			Error::<Test>::NotCreditedAccount
+		);
+		assert_ok!(RightsMgmtPortal::confirm_composition_credit(Origin::signed(2), b"T-034.524.680-1".to_vec(), 0));
+		assert_ok!(RightsMgmtPortal::set_composition_credits(Origin::signed(9), b"T-034.524.680-1".to_vec(), credits));
+		let confirmed: Vec<_> = RightsMgmtPortal::composition_credits_of(b"T-034.524.680-1".to_vec())
+			.iter()
+			.map(Credit::is_confirmed)
+			.collect();
+		assert_eq!(confirmed, vec![true, false]);
+	});
+}
+
+#[test]
+fn validates_party_identifiers() {
+	assert!(is_valid_isni(b"0000000121032683"));
+	assert!(is_valid_isni(b"000000012146438X"));
+	assert!(!is_valid_isni(b"0000000121032684"));
+	assert!(!is_valid_isni(b"00000001210326A3"));
+	assert!(is_valid_ipi(b"00014107363"));
+	assert!(is_valid_ipi(b"12345678908"));
+	assert!(!is_valid_ipi(b"00014107364"));
+}
+
+#[test]
+fn parties_are_looked_up_by_identifier_and_linked_to_credits() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			RightsMgmtPortal::register_party(Origin::signed(1), PartyKind::Writer, b"Writer".to_vec(), Some(*b"0000000121032684"), None),
+			Error::<Test>::InvalidIsni
+		);
+		assert_ok!(RightsMgmtPortal::register_party(
+			Origin::signed(1), PartyKind::Writer, b"Writer".to_vec(), Some(*b"0000000121032683"), Some(*b"00014107363"),
+		));
+		assert_eq!(RightsMgmtPortal::party_by_isni(*b"0000000121032683"), Some(0));
+		assert_eq!(RightsMgmtPortal::party_by_ipi(*b"00014107363"), Some(0));
+		assert_noop!(
+			RightsMgmtPortal::register_party(Origin::signed(2), PartyKind::Artist, b"Other".to_vec(), Some(*b"0000000121032683"), None),
+			Error::<Test>::IsniAlreadyClaimed
+		);
+
+		assert_noop!(
+			RightsMgmtPortal::set_party_identifiers(Origin::signed(2), 0, None, None),
+			Error::<Test>::NotPartyController
+		);
+		assert_ok!(RightsMgmtPortal::set_party_identifiers(Origin::signed(1), 0, None, Some(*b"12345678908")));
+		assert_eq!(RightsMgmtPortal::party_by_isni(*b"0000000121032683"), None);
+		assert_eq!(RightsMgmtPortal::party_by_ipi(*b"00014107363"), None);
+		assert_eq!(RightsMgmtPortal::party_by_ipi(*b"12345678908"), Some(0));
+
+		register(1, &src(b"src-1"));
+		assert_noop!(
+			RightsMgmtPortal::set_credits(Origin::signed(1), src(b"src-1"), vec![
+				Credit::new(ContributorRole::Composer, b"Writer", None).with_party(7),
+			]),
+			Error::<Test>::PartyNotFound
+		);
+		assert_ok!(RightsMgmtPortal::set_credits(Origin::signed(1), src(b"src-1"), vec![
+			Credit::new(ContributorRole::Composer, b"Writer", None).with_party(0),
+		]));
+		assert_eq!(RightsMgmtPortal::credits_of(src(b"src-1"))[0].party(), Some(0));
+	});
+}
+
+#[test]
+fn album_barcodes_are_validated_and_unique() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			RightsMgmtPortal::register_album(Origin::signed(1), b"album-1".to_vec(), 1, album_with_gtin(0, b"036000291453")),
+			Error::<Test>::InvalidGtin
+		);
+		assert_noop!(
+			RightsMgmtPortal::register_album(Origin::signed(1), b"album-1".to_vec(), 1, album_with_gtin(0, b"36000291452")),
+			Error::<Test>::InvalidGtin
+		);
+		assert_ok!(RightsMgmtPortal::register_album(Origin::signed(1), b"album-1".to_vec(), 1, album(0)));
+		assert_ok!(RightsMgmtPortal::register_album(Origin::signed(1), b"album-2".to_vec(), 1, album_with_gtin(0, b"4006381333931")));
+
+		// The EAN-13 form of a registered UPC-A is the same barcode.
+		assert_noop!(
+			RightsMgmtPortal::register_album(Origin::signed(1), b"album-3".to_vec(), 1, album_with_gtin(0, b"0036000291452")),
+			Error::<Test>::GtinExists
+		);
+		assert_eq!(RightsMgmtPortal::album_by_gtin(b"0036000291452"), Some(b"album-1".to_vec()));
+		assert_eq!(RightsMgmtPortal::album_by_gtin(b"4006381333931"), Some(b"album-2".to_vec()));
+	});
+}
+
+#[test]
+fn album_owner_lists_own_tracks() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		register(1, &src(b"src-2"));
+		register(2, &src(b"src-3"));
+		assert_ok!(RightsMgmtPortal::register_album(Origin::signed(1), b"album-1".to_vec(), 1, album(0)));
+		assert_noop!(
+			RightsMgmtPortal::set_album_tracks(Origin::signed(2), b"album-1".to_vec(), vec![src(b"src-3")]),
+			Error::<Test>::NotAlbumOwner
+		);
+		assert_noop!(
+			RightsMgmtPortal::set_album_tracks(Origin::signed(1), b"album-1".to_vec(), vec![src(b"src-1"), src(b"src-3")]),
+			Error::<Test>::NotSrcOwner
+		);
+		assert_noop!(
+			RightsMgmtPortal::set_album_tracks(Origin::signed(1), b"album-1".to_vec(), vec![src(b"src-1"), src(b"src-1")]),
+			Error::<Test>::AlbumDuplicateTrack
+		);
+		assert_ok!(RightsMgmtPortal::set_album_tracks(Origin::signed(1), b"album-1".to_vec(), vec![src(b"src-2"), src(b"src-1")]));
+		assert_eq!(RightsMgmtPortal::album_tracks(b"album-1".to_vec()), vec![src(b"src-2"), src(b"src-1")]);
+	});
+}
+
+#[test]
+fn normalizes_aliases() {
+	assert_eq!(normalize_alias(b"The Band"), b"the band".to_vec());
+	assert_eq!(normalize_alias(b"Band, The"), b"the band".to_vec());
+	assert_eq!(normalize_alias(b"  the   BAND "), b"the band".to_vec());
+	assert_eq!(normalize_alias(b"Theory"), b"theory".to_vec());
+}
+
+#[test]
+fn aliases_resolve_to_canonical_artist() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(RightsMgmtPortal::create_artist(Origin::signed(1), b"The Band".to_vec()));
+		assert_noop!(
+			RightsMgmtPortal::create_artist(Origin::signed(2), b"Band, The".to_vec()),
+			Error::<Test>::AliasTaken
+		);
+		assert_ok!(RightsMgmtPortal::create_artist(Origin::signed(1), b"TB".to_vec()));
+		assert_ok!(RightsMgmtPortal::add_alias(Origin::signed(1), 1, b"T.B.".to_vec()));
+		assert_noop!(
+			RightsMgmtPortal::add_alias(Origin::signed(2), 1, b"Other".to_vec()),
+			Error::<Test>::NotArtistController
+		);
+		assert_eq!(RightsMgmtPortal::resolve_alias(b"band, the"), Some(0));
+		assert_eq!(RightsMgmtPortal::resolve_alias(b"T.B."), Some(1));
+
+		assert_ok!(RightsMgmtPortal::merge_artists(Origin::signed(1), 0, 1));
+		assert_eq!(RightsMgmtPortal::resolve_alias(b"T.B."), Some(0));
+		assert_eq!(RightsMgmtPortal::canonical_artist_id(1), 0);
+		assert_eq!(RightsMgmtPortal::artist(0).unwrap().aliases().len(), 3);
+
+		assert_ok!(RightsMgmtPortal::register_music(
+			Origin::signed(1),
+			src(b"src-1"),
+			b"isrc".to_vec(),
+			1,
+			Some(vec![TestData::new(b"Song", b"TB", b"", b"", b"2021")]),
+		));
+		assert_eq!(RightsMgmtPortal::artists_of_src(b"src-1"), vec![0]);
+
+		assert_noop!(
+			RightsMgmtPortal::split_artist(Origin::signed(1), 0, vec![b"The Band".to_vec()], 1),
+			Error::<Test>::AliasIsCanonicalName
+		);
+		assert_ok!(RightsMgmtPortal::split_artist(Origin::signed(1), 0, vec![b"TB".to_vec(), b"T.B.".to_vec()], 3));
+		assert_eq!(RightsMgmtPortal::resolve_alias(b"TB"), Some(2));
+		assert_eq!(RightsMgmtPortal::artist(2).unwrap().name(), b"TB");
+		assert_eq!(RightsMgmtPortal::artist(2).unwrap().controller(), &3);
+
+		// Artists merged earlier follow their target straight to the new one.
+		assert_ok!(RightsMgmtPortal::create_artist(Origin::signed(1), b"Other".to_vec()));
+		assert_ok!(RightsMgmtPortal::merge_artists(Origin::signed(1), 3, 0));
+		assert_eq!(RightsMgmtPortal::merged_into(1), Some(3));
+		assert_eq!(RightsMgmtPortal::merged_from(3), vec![1, 0]);
+		assert!(RightsMgmtPortal::merged_from(0).is_empty());
+		assert_eq!(RightsMgmtPortal::resolve_alias(b"The Band"), Some(3));
+	});
+}
+
+#[test]
+fn granted_rights_revert_when_expired() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		register(1, &src(b"src-1"));
+		assert_noop!(
+			RightsMgmtPortal::grant_rights(Origin::signed(2), src(b"src-1"), 3, 10),
+			Error::<Test>::NotSrcOwner
+		);
+		let templates = vec![LicenseTemplate::new(LicenseType::Sync, vec![*b"US"], 100, 250)];
+		assert_ok!(RightsMgmtPortal::set_license_templates(Origin::signed(1), src(b"src-1"), templates.clone()));
+		assert_ok!(RightsMgmtPortal::grant_rights(Origin::signed(1), src(b"src-1"), 2, 10));
+		assert_eq!(RightsMgmtPortal::owner_of(src(b"src-1")), Some(2));
+		assert_eq!(RightsMgmtPortal::products_of_org(2), vec![src(b"src-1")]);
+		assert_ok!(RightsMgmtPortal::set_license_templates(
+			Origin::signed(2),
+			src(b"src-1"),
+			vec![LicenseTemplate::new(LicenseType::Streaming, vec![], 10, 1)],
+		));
+
+		assert_noop!(
+			RightsMgmtPortal::grant_rights(Origin::signed(2), src(b"src-1"), 3, 11),
+			Error::<Test>::GrantExceedsParent
+		);
+		assert_ok!(RightsMgmtPortal::grant_rights(Origin::signed(2), src(b"src-1"), 3, 5));
+		assert_eq!(RightsMgmtPortal::grant_chain(src(b"src-1")).len(), 2);
+
+		RightsMgmtPortal::on_initialize(6);
+		assert_eq!(RightsMgmtPortal::owner_of(src(b"src-1")), Some(2));
+		assert_eq!(RightsMgmtPortal::grant_chain(src(b"src-1"))[0].grantee(), &2);
+
+		RightsMgmtPortal::on_initialize(11);
+		assert_eq!(RightsMgmtPortal::owner_of(src(b"src-1")), Some(1));
+		assert!(RightsMgmtPortal::grant_chain(src(b"src-1")).is_empty());
+		assert!(RightsMgmtPortal::products_of_org(2).is_empty());
+		assert_eq!(RightsMgmtPortal::license_templates_of(src(b"src-1")), templates);
+		assert!(RightsMgmtPortal::granted_templates(src(b"src-1")).is_empty());
+	});
+}
+
+#[test]
+fn licenses_are_paid_to_owner_and_verifiable() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		register(1, &src(b"src-1"));
+		let templates = vec![
+			LicenseTemplate::new(LicenseType::Sync, vec![*b"US"], 100, 250),
+			LicenseTemplate::new(LicenseType::Streaming, vec![], 10, 50),
+		];
+		assert_noop!(
+			RightsMgmtPortal::set_license_templates(Origin::signed(2), src(b"src-1"), templates.clone()),
+			Error::<Test>::NotSrcOwner
+		);
+		assert_ok!(RightsMgmtPortal::set_license_templates(Origin::signed(1), src(b"src-1"), templates));
+
+		assert_noop!(
+			RightsMgmtPortal::purchase_license(Origin::signed(2), src(b"src-1"), 0, 200),
+			Error::<Test>::LicensePriceTooHigh
+		);
+		assert_noop!(
+			RightsMgmtPortal::purchase_license(Origin::signed(2), src(b"src-1"), 2, 200),
+			Error::<Test>::LicenseTemplateNotFound
+		);
+		assert_ok!(RightsMgmtPortal::purchase_license(Origin::signed(2), src(b"src-1"), 0, 250));
+		assert_eq!(Balances::free_balance(1), 1_250);
+		assert_eq!(Balances::free_balance(2), 750);
+		assert_eq!(RightsMgmtPortal::licenses_of(2), vec![0]);
+
+		assert!(RightsMgmtPortal::verify_license(0, &2, &src(b"src-1"), LicenseType::Sync, b"US"));
+		assert!(!RightsMgmtPortal::verify_license(0, &2, &src(b"src-1"), LicenseType::Sync, b"DE"));
+		assert!(!RightsMgmtPortal::verify_license(0, &3, &src(b"src-1"), LicenseType::Sync, b"US"));
+		assert!(!RightsMgmtPortal::verify_license(0, &2, &src(b"src-1"), LicenseType::Mechanical, b"US"));
+		System::set_block_number(101);
+		assert!(!RightsMgmtPortal::verify_license(0, &2, &src(b"src-1"), LicenseType::Sync, b"US"));
+	});
+}
+
+#[test]
+fn upheld_takedown_suspends_src_and_forfeits_counter_bond() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		register(1, &src(b"src-1"));
+		let evidence = BlakeTwo256::hash(b"evidence");
+		assert_noop!(
+			RightsMgmtPortal::file_takedown(Origin::signed(2), src(b"src-2"), evidence),
+			Error::<Test>::SrcNotFound
+		);
+		assert_ok!(RightsMgmtPortal::file_takedown(Origin::signed(2), src(b"src-1"), evidence));
+		assert_eq!(Balances::reserved_balance(2), 100);
+		assert!(RightsMgmtPortal::is_suspended(src(b"src-1")));
+		assert!(!RightsMgmtPortal::is_available_in(&src(b"src-1"), b"US", 0));
+		assert_noop!(
+			RightsMgmtPortal::file_takedown(Origin::signed(3), src(b"src-1"), evidence),
+			Error::<Test>::TakedownPending
+		);
+
+		assert_noop!(
+			RightsMgmtPortal::counter_notice(Origin::signed(3), src(b"src-1"), evidence),
+			Error::<Test>::NotSrcOwner
+		);
+		assert_eq!(RightsMgmtPortal::takedown_deadlines(11), vec![src(b"src-1")]);
+		System::set_block_number(3);
+		assert_ok!(RightsMgmtPortal::counter_notice(Origin::signed(1), src(b"src-1"), evidence));
+		assert!(RightsMgmtPortal::takedown_deadlines(11).is_empty());
+		assert_eq!(RightsMgmtPortal::takedown_deadlines(13), vec![src(b"src-1")]);
+		assert_noop!(
+			RightsMgmtPortal::counter_notice(Origin::signed(1), src(b"src-1"), evidence),
+			Error::<Test>::CounterNoticeExists
+		);
+		assert_noop!(
+			RightsMgmtPortal::resolve_takedown(Origin::signed(1), src(b"src-1"), false),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(RightsMgmtPortal::resolve_takedown(Origin::root(), src(b"src-1"), true));
+		assert!(RightsMgmtPortal::takedown_deadlines(13).is_empty());
+		assert_eq!(Balances::free_balance(1), 900);
+		assert_eq!(Balances::free_balance(2), 1_100);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(RightsMgmtPortal::takedown_of(src(b"src-1")).is_none());
+		assert!(RightsMgmtPortal::is_suspended(src(b"src-1")));
+
+		assert_ok!(RightsMgmtPortal::reinstate_src(Origin::root(), src(b"src-1")));
+		assert!(RightsMgmtPortal::is_available_in(&src(b"src-1"), b"US", 0));
+	});
+}
+
+#[test]
+fn takedowns_resolve_on_timeout() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		register(1, &src(b"src-1"));
+		register(1, &src(b"src-2"));
+		let evidence = BlakeTwo256::hash(b"evidence");
+
+		// Unanswered notices are upheld at the deadline.
+		assert_ok!(RightsMgmtPortal::file_takedown(Origin::signed(2), src(b"src-1"), evidence));
+		RightsMgmtPortal::on_initialize(11);
+		assert!(RightsMgmtPortal::takedown_of(src(b"src-1")).is_none());
+		assert!(RightsMgmtPortal::is_suspended(src(b"src-1")));
+		assert_eq!(Balances::free_balance(2), 1_000);
+
+		// Countered notices nobody resolves lapse and reinstate the SRC.
+		System::set_block_number(5);
+		assert_ok!(RightsMgmtPortal::file_takedown(Origin::signed(2), src(b"src-2"), evidence));
+		System::set_block_number(8);
+		assert_ok!(RightsMgmtPortal::counter_notice(Origin::signed(1), src(b"src-2"), evidence));
+		RightsMgmtPortal::on_initialize(15);
+		assert!(RightsMgmtPortal::is_suspended(src(b"src-2")));
+		RightsMgmtPortal::on_initialize(18);
+		assert!(!RightsMgmtPortal::is_suspended(src(b"src-2")));
+		assert_eq!(Balances::free_balance(1), 1_000);
+		assert_eq!(Balances::free_balance(2), 1_000);
+	});
+}
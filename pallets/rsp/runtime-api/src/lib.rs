@@ -0,0 +1,75 @@
+//! Runtime API definition for querying royalty accounting.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+use pallet_royalty::{
+	Escrow, ProposalId, Recoupment, RecoupmentId, SplitGroup, SplitGroupId, SplitProposal, SplitRevision, SrcId, SrcLedger,
+	Statement,
+};
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the royalties paid, claimed and owed per SRC and payee.
+	pub trait RoyaltyApi<AccountId, Balance, AssetId, AssetBalance, BlockNumber> where
+		AccountId: Codec,
+		Balance: Codec,
+		AssetId: Codec,
+		AssetBalance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Lifetime amounts paid, claimed and outstanding for an SRC.
+		fn ledger_of(src_id: SrcId) -> SrcLedger<Balance>;
+
+		/// Royalties accrued to `payee` and not claimed yet, per SRC.
+		fn accrued_of(payee: AccountId) -> Vec<(SrcId, Balance)>;
+
+		/// Lifetime amounts of an asset paid, claimed and outstanding for an SRC.
+		fn asset_ledger_of(src_id: SrcId, asset_id: AssetId) -> SrcLedger<AssetBalance>;
+
+		/// Royalties in assets accrued to `payee` and not claimed yet, per SRC and asset.
+		fn accrued_assets_of(payee: AccountId) -> Vec<(SrcId, AssetId, AssetBalance)>;
+
+		/// Recoupment agreement `recoupment_id`, with its unrecouped balance.
+		fn recoupment(recoupment_id: RecoupmentId) -> Option<Recoupment<AccountId, Balance>>;
+
+		/// Agreements still recouping from the royalties of an SRC, oldest first.
+		fn recoupments_of(src_id: SrcId) -> Vec<(RecoupmentId, Recoupment<AccountId, Balance>)>;
+
+		/// Royalties held in escrow for a suspended SRC.
+		fn escrow_of(src_id: SrcId) -> Option<Escrow<AccountId, Balance, BlockNumber>>;
+
+		/// Royalties in an asset held in escrow for a suspended SRC.
+		fn asset_escrow_of(src_id: SrcId, asset_id: AssetId) -> Option<Escrow<AccountId, AssetBalance, BlockNumber>>;
+
+		/// Statement period of the best block.
+		fn current_period() -> BlockNumber;
+
+		/// Royalties earned by `payee` during statement period `period`, gross and withheld, per SRC.
+		fn statement(payee: AccountId, period: BlockNumber) -> Statement<Balance, AssetId, AssetBalance, BlockNumber>;
+
+		/// Split group `split_group_id`, with its owner and distribution table.
+		fn split_group(split_group_id: SplitGroupId) -> Option<SplitGroup<AccountId>>;
+
+		/// Account receiving withheld royalties, if withholding is enabled.
+		fn tax_account() -> Option<AccountId>;
+
+		/// Part of the royalties of `payee` withheld for the tax account, in basis points.
+		fn withholding_rate_of(payee: AccountId) -> u32;
+
+		/// Smallest payout pushed to `payee`, zero when every payout is pushed.
+		fn payout_threshold_of(payee: AccountId) -> Balance;
+
+		/// Royalties accrued to `payee` across all SRCs and not claimed yet.
+		fn accrued_total_of(payee: AccountId) -> Balance;
+
+		/// Split change proposal `proposal_id`, with its approvers and votes.
+		fn split_proposal(proposal_id: ProposalId) -> Option<SplitProposal<AccountId, BlockNumber>>;
+
+		/// Open split change proposal of an SRC.
+		fn proposal_of(src_id: SrcId) -> Option<ProposalId>;
+
+		/// Distribution tables of an SRC, oldest first, with the block each took effect.
+		fn split_history(src_id: SrcId) -> Vec<SplitRevision<AccountId, BlockNumber>>;
+	}
+}
@@ -1,11 +1,21 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-/// Edit this file to define custom logic or remove it if it is not needed.
-/// Learn more about FRAME and the core library of Substrate FRAME pallets:
-/// https://substrate.dev/docs/en/knowledgebase/runtime/frame
+//! # Royalty Splitter Pallet
+//!
+//! Splits royalty payments for an SRC registered in the rights management pallet among
+//! the payees of its distribution table. Royalties are paid in the native currency or in
+//! any asset of the assets pallet accepted for royalties.
 
-use frame_support::{decl_module, decl_storage, decl_event, decl_error, dispatch, traits::Get};
-use frame_system::ensure_signed;
+use codec::{Decode, Encode};
+use frame_support::{decl_module, decl_storage, decl_event, decl_error, dispatch, ensure, transactional,
+	traits::{Currency, EnsureOrigin, ExistenceRequirement, Get, UnfilteredDispatchable}, weights::Weight};
+use frame_support::storage::with_transaction;
+use frame_support::sp_runtime::{ModuleId, TransactionOutcome, helpers_128bit::multiply_by_rational,
+	traits::{AccountIdConversion, AtLeast32BitUnsigned, One, SaturatedConversion, Saturating, StaticLookup, Zero}};
+use frame_system::{ensure_signed, RawOrigin};
+use pallet_rmp::{AlbumId, BalanceOf, MAX_BASIS_POINTS};
+pub use pallet_rmp::SrcId;
+use sp_std::prelude::*;
 
 #[cfg(test)]
 mod mock;
@@ -13,50 +23,692 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub const SRC_MAX_PAYEES: usize = 64;
+/// Maximum number of split tables a payment cascades through, the SRC's own included.
+pub const SPLIT_MAX_DEPTH: usize = 4;
+/// Maximum number of distribution entries a payment visits across nested split tables.
+pub const SPLIT_MAX_NODES: usize = 128;
+pub const SRC_MAX_RECOUPMENTS: usize = 8;
+pub const RECOUPMENT_MAX_TRACKS: usize = 64;
+pub const ESCROW_MAX_PAYERS: usize = 64;
+/// Maximum number of SRCs a payout run settles for a payee in one step.
+pub const PAYOUT_MAX_SRCS: usize = 16;
+pub const PROPOSAL_MAX_EXPIRIES_PER_BLOCK: usize = 32;
+/// Maximum number of payee statements, or empty periods, pruned per block.
+pub const STATEMENT_MAX_PRUNES_PER_BLOCK: usize = 32;
+
+pub type RecoupmentId = u64;
+pub type SplitGroupId = u64;
+pub type ProposalId = u64;
+
+/// Native currency, the one of the rights management pallet.
+type NativeCurrency<T> = <T as pallet_rmp::Config>::Currency;
+
 /// Configure the pallet by specifying the parameters and types on which it depends.
-pub trait Config: frame_system::Config {
+///
+/// Native royalties are paid in the currency of the rights management pallet, other
+/// royalties in the assets of the assets pallet.
+pub trait Config: frame_system::Config + pallet_rmp::Config + pallet_assets::Config {
 	/// Because this pallet emits events, it depends on the runtime's definition of an event.
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
+
+	/// Identifier of the account holding accrued royalties until they are claimed.
+	type ModuleId: Get<ModuleId>;
+
+	/// Origin allowed to accept an asset for royalty payments.
+	type AssetOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Origin allowed to release escrowed royalties to the rightful owner of an SRC.
+	type EscrowOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Period after which the royalties escrowed for an SRC can be refunded to the payers.
+	type EscrowPeriod: Get<Self::BlockNumber>;
+
+	/// Length in blocks of the periods royalty statements are drawn up for.
+	type StatementPeriod: Get<Self::BlockNumber>;
+
+	/// Number of statement periods kept before the current one; older statements are pruned.
+	type StatementRetention: Get<Self::BlockNumber>;
+
+	/// Origin allowed to set the tax account and the withholding rates of payees.
+	type WithholdingOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Number of blocks between the payout runs settling accrued royalties.
+	type PayoutInterval: Get<Self::BlockNumber>;
+
+	/// Weight a payout run may use per block; the rest of the run resumes in the next block.
+	/// Must leave room for at least one payee to be settled, or runs never progress.
+	type MaxPayoutWeight: Get<Weight>;
+
+	/// Period during which the payees affected by a split change proposal can approve it.
+	type ProposalPeriod: Get<Self::BlockNumber>;
+
+	/// Share of the affected payees, in basis points, whose approval enacts a split change;
+	/// `MAX_BASIS_POINTS` requires unanimous consent.
+	type ApprovalThreshold: Get<u32>;
+}
+
+/// Recipient of a share of royalties.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub enum Payee<AccountId> {
+	/// The share is paid to an account.
+	Account(AccountId),
+	/// The share is split further by the distribution table of another SRC, or paid to
+	/// its owner when it has none.
+	Src(SrcId),
+	/// The share is split further among the members of a split group.
+	Group(SplitGroupId),
+}
+
+impl<AccountId> From<AccountId> for Payee<AccountId> {
+	fn from(account: AccountId) -> Self {
+		Payee::Account(account)
+	}
+}
+
+/// Share of the royalties of an SRC paid to a payee, in basis points.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct Distribution<AccountId> {
+	payee: Payee<AccountId>,
+	bp: u32,
+}
+
+impl<AccountId> Distribution<AccountId> {
+	pub fn new(payee: impl Into<Payee<AccountId>>, bp: u32) -> Self {
+		Self { payee: payee.into(), bp }
+	}
+
+	pub fn payee(&self) -> &Payee<AccountId> {
+		&self.payee
+	}
+
+	pub fn bp(&self) -> u32 {
+		self.bp
+	}
+}
+
+/// Distribution table shared by the SRCs that pay a band, a publisher or another
+/// organisation, managed by its owner.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct SplitGroup<AccountId> {
+	owner: AccountId,
+	distributions: Vec<Distribution<AccountId>>,
+}
+
+impl<AccountId> SplitGroup<AccountId> {
+	pub fn owner(&self) -> &AccountId {
+		&self.owner
+	}
+
+	pub fn distributions(&self) -> &[Distribution<AccountId>] {
+		&self.distributions
+	}
+}
+
+/// Amendment to the distribution table of an SRC, enacted once enough of the payees whose
+/// share it changes approve it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct SplitProposal<AccountId, BlockNumber> {
+	src_id: SrcId,
+	proposer: AccountId,
+	distributions: Vec<Distribution<AccountId>>,
+	/// Accounts asked for consent: the affected payees, or the owners of the affected nested
+	/// SRCs and split groups.
+	approvers: Vec<AccountId>,
+	approvals: Vec<AccountId>,
+	rejections: Vec<AccountId>,
+	expires: BlockNumber,
+}
+
+impl<AccountId, BlockNumber> SplitProposal<AccountId, BlockNumber> {
+	pub fn src_id(&self) -> &SrcId {
+		&self.src_id
+	}
+
+	pub fn proposer(&self) -> &AccountId {
+		&self.proposer
+	}
+
+	pub fn distributions(&self) -> &[Distribution<AccountId>] {
+		&self.distributions
+	}
+
+	pub fn approvers(&self) -> &[AccountId] {
+		&self.approvers
+	}
+
+	pub fn approvals(&self) -> &[AccountId] {
+		&self.approvals
+	}
+
+	pub fn rejections(&self) -> &[AccountId] {
+		&self.rejections
+	}
+
+	pub fn expires(&self) -> &BlockNumber {
+		&self.expires
+	}
+}
+
+/// Distribution table an SRC had from block `effective_from`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct SplitRevision<AccountId, BlockNumber> {
+	effective_from: BlockNumber,
+	distributions: Vec<Distribution<AccountId>>,
+}
+
+impl<AccountId, BlockNumber> SplitRevision<AccountId, BlockNumber> {
+	pub fn effective_from(&self) -> &BlockNumber {
+		&self.effective_from
+	}
+
+	pub fn distributions(&self) -> &[Distribution<AccountId>] {
+		&self.distributions
+	}
+}
+
+/// How the royalties of an SRC reach its payees.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PayoutMode {
+	/// Each payment is transferred to the payees at once.
+	Push,
+	/// Payments are held by the pallet and credited to the payees, who claim them.
+	Accrue,
+}
+
+impl Default for PayoutMode {
+	fn default() -> Self {
+		PayoutMode::Push
+	}
+}
+
+/// Royalties an advance is recouped from.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub enum RecoupmentScope {
+	/// The royalties of a single SRC.
+	Src(SrcId),
+	/// The royalties of the listed tracks of an album, which recoup the advance together.
+	Album(AlbumId, Vec<SrcId>),
+}
+
+impl RecoupmentScope {
+	/// SRCs whose royalties recoup the advance.
+	pub fn srcs(&self) -> &[SrcId] {
+		match self {
+			RecoupmentScope::Src(src_id) => sp_std::slice::from_ref(src_id),
+			RecoupmentScope::Album(_, tracks) => tracks,
+		}
+	}
+}
+
+/// Advance paid by `advancer` and recouped at `rate` basis points of incoming royalties
+/// before they are split.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct Recoupment<AccountId, Balance> {
+	scope: RecoupmentScope,
+	advancer: AccountId,
+	advance: Balance,
+	rate: u32,
+	recouped: Balance,
+}
+
+impl<AccountId, Balance: Saturating + Copy> Recoupment<AccountId, Balance> {
+	pub fn scope(&self) -> &RecoupmentScope {
+		&self.scope
+	}
+
+	pub fn advancer(&self) -> &AccountId {
+		&self.advancer
+	}
+
+	pub fn advance(&self) -> &Balance {
+		&self.advance
+	}
+
+	pub fn rate(&self) -> u32 {
+		self.rate
+	}
+
+	pub fn recouped(&self) -> &Balance {
+		&self.recouped
+	}
+
+	/// Part of the advance still to be recouped.
+	pub fn unrecouped(&self) -> Balance {
+		self.advance.saturating_sub(self.recouped)
+	}
+}
+
+/// Value of an asset accepted for royalties: `asset` units of it are worth `native` units
+/// of the native currency. Advances are recouped from payments in the asset at this rate.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AssetRate<Balance, AssetBalance> {
+	native: Balance,
+	asset: AssetBalance,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy, AssetBalance: AtLeast32BitUnsigned + Copy> AssetRate<Balance, AssetBalance> {
+	pub fn new(native: Balance, asset: AssetBalance) -> Self {
+		Self { native, asset }
+	}
+
+	pub fn native(&self) -> &Balance {
+		&self.native
+	}
+
+	pub fn asset(&self) -> &AssetBalance {
+		&self.asset
+	}
+
+	/// Native value of `amount` of the asset, rounded down.
+	pub fn value_of(&self, amount: AssetBalance) -> Balance {
+		multiply_by_rational(amount.saturated_into(), self.native.saturated_into(), self.asset.saturated_into())
+			.unwrap_or(u128::max_value())
+			.saturated_into()
+	}
+
+	/// Smallest amount of the asset worth at least `value`.
+	pub fn cost_of(&self, value: Balance) -> AssetBalance {
+		let amount: AssetBalance = multiply_by_rational(value.saturated_into(), self.asset.saturated_into(), self.native.saturated_into())
+			.unwrap_or(u128::max_value())
+			.saturated_into();
+		if self.value_of(amount) < value {
+			amount.saturating_add(One::one())
+		} else {
+			amount
+		}
+	}
+}
+
+/// Royalties paid for a suspended SRC, held until the rightful owner is confirmed or
+/// refunded to the payers after `expires`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct Escrow<AccountId, Balance, BlockNumber> {
+	total: Balance,
+	deposits: Vec<(AccountId, Balance)>,
+	expires: BlockNumber,
+}
+
+/// Who the escrows of an SRC are released to, decided when its takedown notice is settled.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub enum EscrowBeneficiary<AccountId> {
+	/// The claimant of an upheld notice, paid the escrows in full.
+	Claimant(AccountId),
+	/// The payees of the SRC, once the notice was dismissed or lapsed.
+	Payees,
+}
+
+impl<AccountId, Balance, BlockNumber> Escrow<AccountId, Balance, BlockNumber> {
+	pub fn total(&self) -> &Balance {
+		&self.total
+	}
+
+	/// Amount held for each payer.
+	pub fn deposits(&self) -> &[(AccountId, Balance)] {
+		&self.deposits
+	}
+
+	pub fn expires(&self) -> &BlockNumber {
+		&self.expires
+	}
+}
+
+/// Royalties earned by a payee, of which `withheld` went to the tax account.
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Earning<Balance> {
+	gross: Balance,
+	withheld: Balance,
+}
+
+impl<Balance: Saturating + Copy> Earning<Balance> {
+	pub fn gross(&self) -> &Balance {
+		&self.gross
+	}
+
+	pub fn withheld(&self) -> &Balance {
+		&self.withheld
+	}
+
+	/// Part of the royalties the payee received.
+	pub fn net(&self) -> Balance {
+		self.gross.saturating_sub(self.withheld)
+	}
+}
+
+/// Royalties earned by a payee during a statement period, per SRC.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct Statement<Balance, AssetId, AssetBalance, BlockNumber> {
+	period: BlockNumber,
+	first_block: BlockNumber,
+	last_block: BlockNumber,
+	earnings: Vec<(SrcId, Earning<Balance>)>,
+	asset_earnings: Vec<(SrcId, AssetId, Earning<AssetBalance>)>,
+}
+
+impl<Balance, AssetId, AssetBalance, BlockNumber> Statement<Balance, AssetId, AssetBalance, BlockNumber> {
+	pub fn period(&self) -> &BlockNumber {
+		&self.period
+	}
+
+	pub fn first_block(&self) -> &BlockNumber {
+		&self.first_block
+	}
+
+	pub fn last_block(&self) -> &BlockNumber {
+		&self.last_block
+	}
+
+	/// Royalties earned in the native currency, by SRC id.
+	pub fn earnings(&self) -> &[(SrcId, Earning<Balance>)] {
+		&self.earnings
+	}
+
+	/// Royalties earned in assets, by SRC id and asset.
+	pub fn asset_earnings(&self) -> &[(SrcId, AssetId, Earning<AssetBalance>)] {
+		&self.asset_earnings
+	}
+}
+
+/// Lifetime royalty accounting of an SRC.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, Debug)]
+pub struct SrcLedger<Balance> {
+	/// Royalties paid for the SRC.
+	paid: Balance,
+	/// Royalties that reached the payees, pushed or claimed.
+	claimed: Balance,
+	/// Royalties accrued to the payees and not claimed yet.
+	outstanding: Balance,
+}
+
+impl<Balance> SrcLedger<Balance> {
+	pub fn paid(&self) -> &Balance {
+		&self.paid
+	}
+
+	pub fn claimed(&self) -> &Balance {
+		&self.claimed
+	}
+
+	pub fn outstanding(&self) -> &Balance {
+		&self.outstanding
+	}
 }
 
 // The pallet's runtime storage items.
 // https://substrate.dev/docs/en/knowledgebase/runtime/storage
 decl_storage! {
-	// A unique name is used to ensure that the pallet's storage items are isolated.
-	// This name may be updated, but each pallet in the runtime must use a unique name.
-	// ---------------------------------vvvvvvvvvvvvvv
-	trait Store for Module<T: Config> as TemplateModule {
-		// Learn more about declaring storage items:
-		// https://substrate.dev/docs/en/knowledgebase/runtime/storage#declaring-storage-items
-		Something get(fn something): Option<u32>;
+	trait Store for Module<T: Config> as RoyaltySplitter {
+		/// Distribution table of each SRC; the shares add up to `MAX_BASIS_POINTS`.
+		pub Distributions get(fn distributions_of): map hasher(blake2_128_concat) SrcId => Vec<Distribution<T::AccountId>>;
+		/// Distribution tables of each SRC, oldest first, with the block each took effect.
+		pub SplitHistory get(fn split_history): map hasher(blake2_128_concat) SrcId => Vec<SplitRevision<T::AccountId, T::BlockNumber>>;
+		pub NextProposalId get(fn next_proposal_id): ProposalId;
+		pub SplitProposals get(fn split_proposal): map hasher(twox_64_concat) ProposalId => Option<SplitProposal<T::AccountId, T::BlockNumber>>;
+		/// Open split change proposal of an SRC.
+		pub ProposalOf get(fn proposal_of): map hasher(blake2_128_concat) SrcId => Option<ProposalId>;
+		pub ProposalExpiries get(fn proposal_expiries): map hasher(twox_64_concat) T::BlockNumber => Vec<ProposalId>;
+		pub NextSplitGroupId get(fn next_split_group_id): SplitGroupId;
+		pub SplitGroups get(fn split_group): map hasher(twox_64_concat) SplitGroupId => Option<SplitGroup<T::AccountId>>;
+		pub PayoutModes get(fn payout_mode_of): map hasher(blake2_128_concat) SrcId => PayoutMode;
+		/// Royalties credited to a payee for an SRC and not claimed yet.
+		pub Accrued get(fn accrued): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) SrcId => BalanceOf<T>;
+		pub Ledgers get(fn ledger_of): map hasher(blake2_128_concat) SrcId => SrcLedger<BalanceOf<T>>;
+		/// Royalties credited to a payee across all SRCs and not claimed yet.
+		pub AccruedTotals get(fn accrued_total_of): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+		/// Smallest payout pushed to a payee; smaller ones accrue until a payout run settles them.
+		pub PayoutThresholds get(fn payout_threshold_of): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
+
+		/// Payees with accrued royalties, in the order payout runs settle them.
+		pub PayoutQueue get(fn payout_queue): map hasher(twox_64_concat) u64 => Option<T::AccountId>;
+		pub PayoutQueueHead get(fn payout_queue_head): u64;
+		pub PayoutQueueTail get(fn payout_queue_tail): u64;
+		pub InPayoutQueue get(fn in_payout_queue): map hasher(blake2_128_concat) T::AccountId => bool;
+		/// End of the queue segment the payout run in progress settles.
+		pub PayoutRunEnd get(fn payout_run_end): Option<u64>;
+
+		/// Assets accepted for royalty payments.
+		pub RoyaltyAssets get(fn is_royalty_asset): map hasher(blake2_128_concat) T::AssetId => bool;
+		pub AssetRates get(fn asset_rate_of): map hasher(blake2_128_concat) T::AssetId => Option<AssetRate<BalanceOf<T>, T::Balance>>;
+		/// Royalties in an asset credited to a payee for an SRC and not claimed yet.
+		pub AccruedAssets get(fn accrued_asset): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) (SrcId, T::AssetId) => T::Balance;
+		pub AssetLedgers get(fn asset_ledger_of): double_map hasher(blake2_128_concat) SrcId, hasher(blake2_128_concat) T::AssetId => SrcLedger<T::Balance>;
+
+		/// Royalties earned by a payee for an SRC during a statement period.
+		pub Earnings get(fn earnings): double_map hasher(blake2_128_concat) (T::AccountId, T::BlockNumber), hasher(blake2_128_concat) SrcId => Earning<BalanceOf<T>>;
+		/// Royalties in an asset earned by a payee for an SRC during a statement period.
+		pub AssetEarnings get(fn asset_earnings): double_map hasher(blake2_128_concat) (T::AccountId, T::BlockNumber), hasher(blake2_128_concat) (SrcId, T::AssetId) => Earning<T::Balance>;
+		/// Payees with a statement for a period, until it is pruned.
+		pub StatementPayees get(fn has_statement): double_map hasher(twox_64_concat) T::BlockNumber, hasher(blake2_128_concat) T::AccountId => bool;
+		/// Oldest statement period not pruned yet.
+		pub FirstRetainedPeriod get(fn first_retained_period): T::BlockNumber;
+
+		/// Account receiving the royalties withheld from payees.
+		pub TaxAccount get(fn tax_account): Option<T::AccountId>;
+		/// Part of their royalties withheld from payees, in basis points.
+		pub WithholdingRates get(fn withholding_rate_of): map hasher(blake2_128_concat) T::AccountId => u32;
+
+		pub Escrows get(fn escrow_of): map hasher(blake2_128_concat) SrcId => Option<Escrow<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+		pub AssetEscrows get(fn asset_escrow_of): double_map hasher(blake2_128_concat) SrcId, hasher(blake2_128_concat) T::AssetId => Option<Escrow<T::AccountId, T::Balance, T::BlockNumber>>;
+		/// Beneficiary of the escrows of an SRC, set when its last takedown notice was settled.
+		pub EscrowBeneficiaries get(fn escrow_beneficiary_of): map hasher(blake2_128_concat) SrcId => Option<EscrowBeneficiary<T::AccountId>>;
+
+		pub NextRecoupmentId get(fn next_recoupment_id): RecoupmentId;
+		pub Recoupments get(fn recoupment): map hasher(twox_64_concat) RecoupmentId => Option<Recoupment<T::AccountId, BalanceOf<T>>>;
+		/// Agreements still recouping from the royalties of an SRC, oldest first.
+		pub RecoupmentsOf get(fn recoupments_of): map hasher(blake2_128_concat) SrcId => Vec<RecoupmentId>;
+	}
+	add_extra_genesis {
+		build(|_config| {
+			// Keep the pallet account above the existential deposit so claims never reap it.
+			let account_id = <Module<T>>::account_id();
+			let min = NativeCurrency::<T>::minimum_balance();
+			if NativeCurrency::<T>::free_balance(&account_id) < min {
+				let _ = NativeCurrency::<T>::make_free_balance_be(&account_id, min);
+			}
+		});
 	}
 }
 
 // Pallets use events to inform users when important changes are made.
 // https://substrate.dev/docs/en/knowledgebase/runtime/events
 decl_event!(
-	pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId {
-		/// Event documentation should end with an array that provides descriptive names for event
-		/// parameters. [something, who]
-		SomethingStored(u32, AccountId),
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Config>::AccountId,
+		Balance = BalanceOf<T>,
+		BlockNumber = <T as frame_system::Config>::BlockNumber,
+		AssetId = <T as pallet_assets::Config>::AssetId,
+		AssetBalance = <T as pallet_assets::Config>::Balance,
+	{
+		/// The distribution table of an SRC was replaced. [who, src_id]
+		DistributionsSet(AccountId, SrcId),
+
+		/// A change to the distribution table of an SRC was proposed. [proposer, proposal_id, src_id, expires]
+		SplitChangeProposed(AccountId, ProposalId, SrcId, BlockNumber),
+
+		/// An affected payee approved a split change. [approver, proposal_id]
+		SplitChangeApproved(AccountId, ProposalId),
+
+		/// An affected payee rejected a split change. [approver, proposal_id]
+		SplitChangeRejected(AccountId, ProposalId),
+
+		/// A split change was approved and replaced the distribution table. [proposal_id, src_id]
+		SplitChangeEnacted(ProposalId, SrcId),
+
+		/// Too many affected payees rejected a split change for it to pass. [proposal_id]
+		SplitChangeDefeated(ProposalId),
+
+		/// A split change was not approved in time. [proposal_id]
+		SplitChangeExpired(ProposalId),
+
+		/// A split change was withdrawn, or cancelled as its SRC or an approver changed hands. [proposal_id]
+		SplitChangeCancelled(ProposalId),
+
+		/// A split group was created. [owner, split_group_id]
+		SplitGroupCreated(AccountId, SplitGroupId),
+
+		/// The distribution table of a split group was replaced. [owner, split_group_id]
+		SplitGroupUpdated(AccountId, SplitGroupId),
+
+		/// The payout mode of an SRC was changed. [who, src_id, mode]
+		PayoutModeSet(AccountId, SrcId, PayoutMode),
 
-        /// Alerting the system that royalties have been split
-        RoyaltySplit(u32, Vec<AccountId>),
+		/// A royalty payment was split among the payees. [payer, src_id, amount, payouts]
+		RoyaltySplit(AccountId, SrcId, Balance, Vec<(AccountId, Balance)>),
+
+		/// A royalty payment was credited to the payees' ledger. [payer, src_id, amount, payouts]
+		RoyaltyAccrued(AccountId, SrcId, Balance, Vec<(AccountId, Balance)>),
+
+		/// A payee withdrew the royalties accrued for an SRC. [payee, src_id, amount]
+		RoyaltyClaimed(AccountId, SrcId, Balance),
+
+		/// Royalties paid for a suspended SRC were put in escrow. [payer, src_id, amount, expires]
+		RoyaltyEscrowed(AccountId, SrcId, Balance, BlockNumber),
+
+		/// The escrow of an SRC was split among its payees once its dispute was resolved. [src_id, amount]
+		EscrowReleased(SrcId, Balance),
+
+		/// The escrow of an SRC was paid to the claimant of an upheld takedown notice. [src_id, claimant, amount]
+		EscrowPaidToClaimant(SrcId, AccountId, Balance),
+
+		/// The escrow of an SRC expired and was refunded to the payers. [src_id, amount]
+		EscrowRefunded(SrcId, Balance),
+
+		/// A deposit of an expired escrow could not be refunded and stays in escrow. [src_id, payer, amount]
+		EscrowRefundFailed(SrcId, AccountId, Balance),
+
+		/// Royalties paid in an asset for a suspended SRC were put in escrow. [payer, src_id, asset_id, amount, expires]
+		AssetRoyaltyEscrowed(AccountId, SrcId, AssetId, AssetBalance, BlockNumber),
+
+		/// The escrow of an SRC in an asset was split among its payees once its dispute was resolved. [src_id, asset_id, amount]
+		AssetEscrowReleased(SrcId, AssetId, AssetBalance),
+
+		/// The escrow of an SRC in an asset was paid to the claimant of an upheld takedown notice. [src_id, asset_id, claimant, amount]
+		AssetEscrowPaidToClaimant(SrcId, AssetId, AccountId, AssetBalance),
+
+		/// The escrow of an SRC in an asset expired and was refunded to the payers. [src_id, asset_id, amount]
+		AssetEscrowRefunded(SrcId, AssetId, AssetBalance),
+
+		/// A deposit of an expired escrow in an asset could not be refunded and stays in escrow. [src_id, asset_id, payer, amount]
+		AssetEscrowRefundFailed(SrcId, AssetId, AccountId, AssetBalance),
+
+		/// An asset was accepted for royalty payments, or its rate changed. [asset_id, native, asset]
+		RoyaltyAssetRegistered(AssetId, Balance, AssetBalance),
+
+		/// A royalty payment in an asset was split among the payees. [payer, src_id, asset_id, amount, payouts]
+		AssetRoyaltySplit(AccountId, SrcId, AssetId, AssetBalance, Vec<(AccountId, AssetBalance)>),
+
+		/// Royalties in an asset were credited to the payees' ledger. [payer, src_id, asset_id, amount, payouts]
+		AssetRoyaltyAccrued(AccountId, SrcId, AssetId, AssetBalance, Vec<(AccountId, AssetBalance)>),
+
+		/// A payee withdrew the royalties in an asset accrued for an SRC. [payee, src_id, asset_id, amount]
+		AssetRoyaltyClaimed(AccountId, SrcId, AssetId, AssetBalance),
+
+		/// A recoupment agreement was made for an advance. [who, recoupment_id, advancer, advance]
+		RecoupmentCreated(AccountId, RecoupmentId, AccountId, Balance),
+
+		/// Royalties paid down an advance. [recoupment_id, amount, unrecouped]
+		AdvanceRecouped(RecoupmentId, Balance, Balance),
+
+		/// Royalties in an asset paid down an advance, the unrecouped part being in the native
+		/// currency. [recoupment_id, asset_id, amount, unrecouped]
+		AdvanceRecoupedInAsset(RecoupmentId, AssetId, AssetBalance, Balance),
+
+		/// The account receiving withheld royalties was changed. [tax_account]
+		TaxAccountSet(AccountId),
+
+		/// The withholding rate of a payee was changed. [payee, rate]
+		WithholdingRateSet(AccountId, u32),
+
+		/// A payee changed their payout threshold. [payee, threshold]
+		PayoutThresholdSet(AccountId, Balance),
+
+		/// A payout run started settling the queued payees. [payees]
+		PayoutRunStarted(u64),
+
+		/// A payout run settled royalties accrued to a payee. [payee, amount]
+		PayoutSettled(AccountId, Balance),
+
+		/// A payout run could not transfer royalties accrued to a payee, who left the queue
+		/// until credited again. [payee, amount]
+		PayoutFailed(AccountId, Balance),
+
+		/// A payout run went through all of its payees.
+		PayoutRunCompleted,
 	}
 );
 
 // Errors inform users that something went wrong.
 decl_error! {
 	pub enum Error for Module<T: Config> {
-		/// Error names should be descriptive.
-		NoneValue,
-		/// Errors should have helpful documentation associated with them.
-		StorageOverflow,
-
-        /// Errors that could happen while splitting:
-        NegativeBalance, // If someone tries to split a negative balance
-        TokenNotAvailable, // If those tokens cannot be created
-
+		/// The SRC is not registered.
+		SrcNotFound,
+		/// The SRC is suspended by a takedown notice.
+		SrcSuspended,
+		/// A takedown notice against the SRC is still open.
+		TakedownPending,
+		/// The SRC has no escrowed royalties.
+		EscrowNotFound,
+		/// The escrow of the SRC has not expired yet.
+		EscrowNotExpired,
+		/// The escrow of the SRC already holds royalties of `ESCROW_MAX_PAYERS` payers.
+		EscrowFull,
+		/// The SRC has no distribution table.
+		NoDistributions,
+		/// A distribution table has no payee or more than `SRC_MAX_PAYEES`.
+		InvalidPayeeCount,
+		/// A share is zero or the shares do not add up to `MAX_BASIS_POINTS`.
+		InvalidShares,
+		/// A payee appears twice in a distribution table.
+		DuplicatePayee,
+		/// The SRC already has a distribution table, which only changes through a proposal.
+		SplitChangeRequiresProposal,
+		/// The SRC already has an open split change proposal.
+		ProposalPending,
+		/// The split change proposal does not exist or is closed.
+		ProposalNotFound,
+		/// The caller's consent is not asked for the split change.
+		NotApprover,
+		/// The caller already approved or rejected the split change.
+		AlreadyVoted,
+		/// Only the proposer may withdraw the split change.
+		NotProposer,
+		/// Too many proposals expire in the same block.
+		ProposalScheduleFull,
+		/// The split group does not exist.
+		SplitGroupNotFound,
+		/// The caller does not own the split group.
+		NotSplitGroupOwner,
+		/// A distribution table pays itself through nested split tables.
+		SplitCycle,
+		/// Royalties cascade through more than `SPLIT_MAX_DEPTH` split tables.
+		SplitTooDeep,
+		/// Royalties cascade through more than `SPLIT_MAX_NODES` distribution entries.
+		SplitTooLarge,
+		/// Nothing to split.
+		ZeroAmount,
+		/// No royalties accrued for the caller on this SRC.
+		NothingToClaim,
+		/// The asset is not accepted for royalty payments.
+		AssetNotAccepted,
+		/// The pallet account does not hold the minimum balance of the asset.
+		AssetReserveMissing,
+		/// One side of the asset rate is zero.
+		InvalidAssetRate,
+		/// The payment would leave the payer with less than the asset's minimum balance.
+		PayerBelowMinimum,
+		/// The album is not registered or the caller does not own it.
+		NotAlbumOwner,
+		/// The advance is zero, the rate is not in 1..=`MAX_BASIS_POINTS` or the track list is
+		/// empty, longer than `RECOUPMENT_MAX_TRACKS` or has duplicates.
+		InvalidRecoupment,
+		/// An SRC already recoups `SRC_MAX_RECOUPMENTS` advances.
+		TooManyRecoupments,
+		/// A track is not on the track list of the album.
+		TrackNotOnAlbum,
+		/// The withholding rate is above `MAX_BASIS_POINTS`.
+		InvalidWithholdingRate,
+		/// No tax account is set to receive withheld royalties.
+		NoTaxAccount,
 	}
 }
 
@@ -71,41 +723,1472 @@ decl_module! {
 		// Events must be initialized if they are used by the pallet.
 		fn deposit_event() = default;
 
-		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
-		#[weight = 10_000 + T::DbWeight::get().writes(1)]
-		pub fn do_something(origin, something: u32) -> dispatch::DispatchResult {
-			// Check that the extrinsic was signed and get the signer.
-			// This function will return an error if the extrinsic is not signed.
-			// https://substrate.dev/docs/en/knowledgebase/runtime/origin
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let expiring = <ProposalExpiries<T>>::take(now);
+			let mut expired = 0u64;
+			for proposal_id in &expiring {
+				if let Some(proposal) = <SplitProposals<T>>::take(proposal_id) {
+					ProposalOf::remove(&proposal.src_id);
+					expired += 1;
+					Self::deposit_event(RawEvent::SplitChangeExpired(*proposal_id));
+				}
+			}
+			let mut weight = T::DbWeight::get().reads_writes(2 + expiring.len() as u64, 1 + 2 * expired);
+
+			let interval = T::PayoutInterval::get();
+			if !interval.is_zero() && (now % interval).is_zero() && PayoutRunEnd::get().is_none() {
+				let tail = Self::payout_queue_tail();
+				PayoutRunEnd::put(tail);
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 1));
+				Self::deposit_event(RawEvent::PayoutRunStarted(tail.saturating_sub(Self::payout_queue_head())));
+			}
+			weight = weight.saturating_add(Self::prune_statements());
+			weight.saturating_add(Self::run_payouts(T::MaxPayoutWeight::get()))
+		}
+
+		fn integrity_test() {
+			assert!(
+				T::MaxPayoutWeight::get() >= T::DbWeight::get().reads_writes(2, 2) + Self::payout_step_weight(),
+				"MaxPayoutWeight must fit one step of a payout run",
+			);
+		}
+
+		/// Set the first distribution table of an SRC. Only the SRC owner may call this; later
+		/// changes go through `propose_split_change`.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2 + SPLIT_MAX_NODES as u64, 2)]
+		pub fn set_distributions(origin, src_id: SrcId, distributions: Vec<Distribution<T::AccountId>>) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			<pallet_rmp::Module<T>>::ensure_src_owner(&who, &src_id)?;
+			ensure!(!<Distributions<T>>::contains_key(&src_id), Error::<T>::SplitChangeRequiresProposal);
+			Self::validate_distributions(&distributions)?;
+			Self::validate_nesting(Payee::Src(src_id.clone()), &distributions)?;
+
+			Self::apply_distributions(&src_id, distributions);
+
+			Self::deposit_event(RawEvent::DistributionsSet(who, src_id));
+			Ok(())
+		}
+
+		/// Propose to replace the distribution table of an SRC. The change is enacted once
+		/// `ApprovalThreshold` of the payees whose share it changes, adds or removes approve it, and
+		/// expires after `ProposalPeriod` blocks or when the SRC changes hands. Only the SRC
+		/// owner may call this; an affected owner approves by proposing.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(6 + 2 * SRC_MAX_PAYEES as u64 + SPLIT_MAX_NODES as u64, 6)]
+		#[transactional]
+		pub fn propose_split_change(
+			origin,
+			src_id: SrcId,
+			distributions: Vec<Distribution<T::AccountId>>,
+		) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			<pallet_rmp::Module<T>>::ensure_src_owner(&who, &src_id)?;
+			let current = Self::distributions_of(&src_id);
+			ensure!(!current.is_empty(), Error::<T>::NoDistributions);
+			ensure!(Self::proposal_of(&src_id).is_none(), Error::<T>::ProposalPending);
+			Self::validate_distributions(&distributions)?;
+			Self::validate_nesting(Payee::Src(src_id.clone()), &distributions)?;
+			let approvers = Self::affected_approvers(&current, &distributions)?;
+
+			let expires = <frame_system::Module<T>>::block_number().saturating_add(T::ProposalPeriod::get());
+			ensure!(
+				<ProposalExpiries<T>>::decode_len(expires).unwrap_or(0) < PROPOSAL_MAX_EXPIRIES_PER_BLOCK,
+				Error::<T>::ProposalScheduleFull
+			);
+			let proposal_id = Self::next_proposal_id();
+			let approvals = approvers.iter().filter(|approver| *approver == &who).cloned().collect();
+			let proposal = SplitProposal {
+				src_id: src_id.clone(),
+				proposer: who.clone(),
+				distributions,
+				approvers,
+				approvals,
+				rejections: Vec::new(),
+				expires,
+			};
+			NextProposalId::put(proposal_id + 1);
+			Self::deposit_event(RawEvent::SplitChangeProposed(who, proposal_id, src_id.clone(), expires));
+
+			if Self::is_approved(&proposal) {
+				return Self::enact(proposal_id, proposal);
+			}
+			<ProposalExpiries<T>>::append(expires, proposal_id);
+			ProposalOf::insert(&src_id, proposal_id);
+			<SplitProposals<T>>::insert(proposal_id, proposal);
+			Ok(())
+		}
+
+		/// Approve a split change the caller's consent is asked for. A proposal whose approvers
+		/// changed since it was made, as a nested SRC or split group changed hands, is
+		/// cancelled instead.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3 + 2 * SRC_MAX_PAYEES as u64 + SPLIT_MAX_NODES as u64, 4)]
+		pub fn approve_split_change(origin, proposal_id: ProposalId) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut proposal = match Self::voting_proposal(&who, proposal_id)? {
+				Some(proposal) => proposal,
+				None => return Ok(()),
+			};
+			proposal.approvals.push(who.clone());
+
+			Self::deposit_event(RawEvent::SplitChangeApproved(who, proposal_id));
+			if Self::is_approved(&proposal) {
+				return Self::enact(proposal_id, proposal);
+			}
+			<SplitProposals<T>>::insert(proposal_id, proposal);
+			Ok(())
+		}
+
+		/// Reject a split change the caller's consent is asked for. The proposal is closed as
+		/// soon as it can no longer be approved, and cancelled as by `approve_split_change`
+		/// when its approvers changed.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2 + 2 * SRC_MAX_PAYEES as u64, 2)]
+		pub fn reject_split_change(origin, proposal_id: ProposalId) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut proposal = match Self::voting_proposal(&who, proposal_id)? {
+				Some(proposal) => proposal,
+				None => return Ok(()),
+			};
+			proposal.rejections.push(who.clone());
+
+			Self::deposit_event(RawEvent::SplitChangeRejected(who, proposal_id));
+			let not_rejected = proposal.approvers.len() - proposal.rejections.len();
+			if !Self::threshold_reached(not_rejected, proposal.approvers.len()) {
+				<SplitProposals<T>>::remove(proposal_id);
+				ProposalOf::remove(&proposal.src_id);
+				Self::deposit_event(RawEvent::SplitChangeDefeated(proposal_id));
+				return Ok(());
+			}
+			<SplitProposals<T>>::insert(proposal_id, proposal);
+			Ok(())
+		}
+
+		/// Withdraw a split change. Only the proposer may call this.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 2)]
+		pub fn cancel_split_change(origin, proposal_id: ProposalId) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let proposal = Self::split_proposal(proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+			ensure!(proposal.proposer == who, Error::<T>::NotProposer);
+			<SplitProposals<T>>::remove(proposal_id);
+			ProposalOf::remove(&proposal.src_id);
+
+			Self::deposit_event(RawEvent::SplitChangeCancelled(proposal_id));
+			Ok(())
+		}
+
+		/// Create a split group owned by the caller, which distribution tables reference
+		/// with `Payee::Group`.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1 + SPLIT_MAX_NODES as u64, 2)]
+		pub fn create_split_group(origin, distributions: Vec<Distribution<T::AccountId>>) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let split_group_id = Self::next_split_group_id();
+			Self::validate_distributions(&distributions)?;
+			Self::validate_nesting(Payee::Group(split_group_id), &distributions)?;
+
+			<SplitGroups<T>>::insert(split_group_id, SplitGroup { owner: who.clone(), distributions });
+			NextSplitGroupId::put(split_group_id + 1);
+
+			Self::deposit_event(RawEvent::SplitGroupCreated(who, split_group_id));
+			Ok(())
+		}
+
+		/// Replace the distribution table of a split group. Only the group owner may call this.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1 + SPLIT_MAX_NODES as u64, 1)]
+		pub fn update_split_group(
+			origin,
+			split_group_id: SplitGroupId,
+			distributions: Vec<Distribution<T::AccountId>>,
+		) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut split_group = Self::split_group(split_group_id).ok_or(Error::<T>::SplitGroupNotFound)?;
+			ensure!(split_group.owner == who, Error::<T>::NotSplitGroupOwner);
+			Self::validate_distributions(&distributions)?;
+			Self::validate_nesting(Payee::Group(split_group_id), &distributions)?;
+
+			split_group.distributions = distributions;
+			<SplitGroups<T>>::insert(split_group_id, split_group);
+
+			Self::deposit_event(RawEvent::SplitGroupUpdated(who, split_group_id));
+			Ok(())
+		}
+
+		/// Choose whether the royalties of an SRC are pushed to the payees or accrue until
+		/// claimed. Only the SRC owner may call this.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn set_payout_mode(origin, src_id: SrcId, mode: PayoutMode) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			<pallet_rmp::Module<T>>::ensure_src_owner(&who, &src_id)?;
+			PayoutModes::insert(&src_id, mode);
+
+			Self::deposit_event(RawEvent::PayoutModeSet(who, src_id, mode));
+			Ok(())
+		}
+
+		/// Pay `amount` of royalties for an SRC. Cleared samples get their share first, then
+		/// the advances being recouped, and the rest is split among the payees of the
+		/// distribution table. Royalties paid for
+		/// an SRC suspended by a takedown notice are held in escrow instead. In push mode,
+		/// payouts below the payout threshold of their payee, or too small to keep the
+		/// payee's account alive, accrue to the payee.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(
+			8 + (1 + SPLIT_MAX_NODES as u64) * (SRC_MAX_RECOUPMENTS as u64 + 7 + 2 * ESCROW_MAX_PAYERS as u64),
+			5 + (1 + SPLIT_MAX_NODES as u64) * (SRC_MAX_RECOUPMENTS as u64 * (2 + RECOUPMENT_MAX_TRACKS as u64) + 7 + 2 * ESCROW_MAX_PAYERS as u64),
+		)]
+		#[transactional]
+		pub fn pay_royalty(origin, src_id: SrcId, amount: BalanceOf<T>) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(<pallet_rmp::Module<T>>::owner_of(&src_id).is_some(), Error::<T>::SrcNotFound);
+			if <pallet_rmp::Module<T>>::is_suspended(&src_id) {
+				return Self::hold_in_escrow(who, src_id, amount);
+			}
+			Self::split_royalty(who, src_id, amount)
+		}
+
+		/// Withdraw the royalties accrued to the caller for an SRC.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(4, 4)]
+		#[transactional]
+		pub fn claim(origin, src_id: SrcId) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let amount = <Accrued<T>>::take(&who, &src_id);
+			ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+			<AccruedTotals<T>>::mutate(&who, |total| *total = total.saturating_sub(amount));
+			NativeCurrency::<T>::transfer(&Self::account_id(), &who, amount, ExistenceRequirement::KeepAlive)?;
+			<Ledgers<T>>::mutate(&src_id, |ledger| {
+				ledger.claimed = ledger.claimed.saturating_add(amount);
+				ledger.outstanding = ledger.outstanding.saturating_sub(amount);
+			});
+
+			Self::deposit_event(RawEvent::RoyaltyClaimed(who, src_id, amount));
+			Ok(())
+		}
+
+		/// Pay out the royalties escrowed for an SRC once its takedown notice is closed. The
+		/// claimant of an upheld notice is paid the whole escrow, credited if the transfer
+		/// fails. Otherwise the SRC must no longer be suspended and the escrow is split as a
+		/// payment made now would be, so cleared samples, recoupment agreements, nested split
+		/// tables and withholding apply; no nested SRC may be suspended.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(
+			10 + (1 + SPLIT_MAX_NODES as u64) * (SRC_MAX_RECOUPMENTS as u64 + 7),
+			6 + (1 + SPLIT_MAX_NODES as u64) * (SRC_MAX_RECOUPMENTS as u64 * (2 + RECOUPMENT_MAX_TRACKS as u64) + 7),
+		)]
+		#[transactional]
+		pub fn release_escrow(origin, src_id: SrcId) -> dispatch::DispatchResult {
+			T::EscrowOrigin::ensure_origin(origin)?;
+
+			let escrow = Self::escrow_of(&src_id).ok_or(Error::<T>::EscrowNotFound)?;
+			ensure!(<pallet_rmp::Module<T>>::takedown_of(&src_id).is_none(), Error::<T>::TakedownPending);
+			<Escrows<T>>::remove(&src_id);
+			if let Some(EscrowBeneficiary::Claimant(claimant)) = Self::escrow_beneficiary_of(&src_id) {
+				if !Self::try_transfer(&Self::account_id(), &claimant, escrow.total) {
+					Self::credit(&claimant, &src_id, escrow.total);
+				}
+				Self::deposit_event(RawEvent::EscrowPaidToClaimant(src_id, claimant, escrow.total));
+				return Ok(());
+			}
+			ensure!(!<pallet_rmp::Module<T>>::is_suspended(&src_id), Error::<T>::SrcSuspended);
+			Self::split_royalty(Self::account_id(), src_id.clone(), escrow.total)?;
+
+			Self::deposit_event(RawEvent::EscrowReleased(src_id, escrow.total));
+			Ok(())
+		}
+
+		/// Refund the royalties escrowed for an SRC to their payers once the escrow expired.
+		/// Each payer is refunded on their own; the deposits that fail to transfer stay in the
+		/// escrow, to be refunded by a later call.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2 + ESCROW_MAX_PAYERS as u64, 2 + ESCROW_MAX_PAYERS as u64)]
+		#[transactional]
+		pub fn refund_escrow(origin, src_id: SrcId) -> dispatch::DispatchResult {
+			ensure_signed(origin)?;
+
+			let escrow = Self::escrow_of(&src_id).ok_or(Error::<T>::EscrowNotFound)?;
+			let now = <frame_system::Module<T>>::block_number();
+			ensure!(now >= escrow.expires, Error::<T>::EscrowNotExpired);
+			let account_id = Self::account_id();
+			let (kept, refunded) = Self::refund_deposits(escrow.deposits, |payer, deposit| {
+				Self::try_transfer(&account_id, payer, deposit)
+			});
+			for (payer, deposit) in &kept {
+				Self::deposit_event(RawEvent::EscrowRefundFailed(src_id.clone(), payer.clone(), *deposit));
+			}
+			match Self::remaining_escrow(kept, escrow.expires) {
+				Some(remaining) => <Escrows<T>>::insert(&src_id, remaining),
+				None => <Escrows<T>>::remove(&src_id),
+			}
+
+			Self::deposit_event(RawEvent::EscrowRefunded(src_id, refunded));
+			Ok(())
+		}
+
+		/// Pay out the royalties escrowed in an asset for an SRC, as `release_escrow` does.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(10 + 7 * SPLIT_MAX_NODES as u64, 4 + 4 * SPLIT_MAX_NODES as u64)]
+		#[transactional]
+		pub fn release_asset_escrow(origin, src_id: SrcId, asset_id: T::AssetId) -> dispatch::DispatchResult {
+			T::EscrowOrigin::ensure_origin(origin)?;
+
+			let escrow = Self::asset_escrow_of(&src_id, asset_id).ok_or(Error::<T>::EscrowNotFound)?;
+			ensure!(<pallet_rmp::Module<T>>::takedown_of(&src_id).is_none(), Error::<T>::TakedownPending);
+			<AssetEscrows<T>>::remove(&src_id, asset_id);
+			if let Some(EscrowBeneficiary::Claimant(claimant)) = Self::escrow_beneficiary_of(&src_id) {
+				if !Self::try_transfer_asset(asset_id, &Self::account_id(), &claimant, escrow.total) {
+					<AccruedAssets<T>>::mutate(&claimant, (&src_id, asset_id), |total| *total = total.saturating_add(escrow.total));
+				}
+				Self::deposit_event(RawEvent::AssetEscrowPaidToClaimant(src_id, asset_id, claimant, escrow.total));
+				return Ok(());
+			}
+			ensure!(!<pallet_rmp::Module<T>>::is_suspended(&src_id), Error::<T>::SrcSuspended);
+			Self::split_royalty_in_asset(Self::account_id(), src_id.clone(), asset_id, escrow.total)?;
+
+			Self::deposit_event(RawEvent::AssetEscrowReleased(src_id, asset_id, escrow.total));
+			Ok(())
+		}
+
+		/// Refund the royalties escrowed in an asset for an SRC to their payers once the
+		/// escrow expired, as `refund_escrow` does.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(2 + 3 * ESCROW_MAX_PAYERS as u64, 2 + 3 * ESCROW_MAX_PAYERS as u64)]
+		#[transactional]
+		pub fn refund_asset_escrow(origin, src_id: SrcId, asset_id: T::AssetId) -> dispatch::DispatchResult {
+			ensure_signed(origin)?;
+
+			let escrow = Self::asset_escrow_of(&src_id, asset_id).ok_or(Error::<T>::EscrowNotFound)?;
+			let now = <frame_system::Module<T>>::block_number();
+			ensure!(now >= escrow.expires, Error::<T>::EscrowNotExpired);
+			let account_id = Self::account_id();
+			let (kept, refunded) = Self::refund_deposits(escrow.deposits, |payer, deposit| {
+				Self::try_transfer_asset(asset_id, &account_id, payer, deposit)
+			});
+			for (payer, deposit) in &kept {
+				Self::deposit_event(RawEvent::AssetEscrowRefundFailed(src_id.clone(), asset_id, payer.clone(), *deposit));
+			}
+			match Self::remaining_escrow(kept, escrow.expires) {
+				Some(remaining) => <AssetEscrows<T>>::insert(&src_id, asset_id, remaining),
+				None => <AssetEscrows<T>>::remove(&src_id, asset_id),
+			}
+
+			Self::deposit_event(RawEvent::AssetEscrowRefunded(src_id, asset_id, refunded));
+			Ok(())
+		}
+
+		/// Record an advance paid by `advancer`, recouped at `rate` basis points of the native
+		/// royalties of the SRC or album tracks in `scope`, once their cleared samples had their
+		/// share and before they are split. Only the owner of the SRC, or of the album and all
+		/// the listed tracks, may call this.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3 + 2 * RECOUPMENT_MAX_TRACKS as u64, 2 + RECOUPMENT_MAX_TRACKS as u64)]
+		pub fn create_recoupment(
+			origin,
+			scope: RecoupmentScope,
+			advancer: T::AccountId,
+			advance: BalanceOf<T>,
+			rate: u32,
+		) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!advance.is_zero() && rate > 0 && rate <= MAX_BASIS_POINTS, Error::<T>::InvalidRecoupment);
+			if let RecoupmentScope::Album(album_id, tracks) = &scope {
+				ensure!(
+					<pallet_rmp::Module<T>>::album_owner_of(album_id).as_ref() == Some(&who),
+					Error::<T>::NotAlbumOwner
+				);
+				ensure!(!tracks.is_empty() && tracks.len() <= RECOUPMENT_MAX_TRACKS, Error::<T>::InvalidRecoupment);
+				let album_tracks = <pallet_rmp::Module<T>>::album_tracks(album_id);
+				for (i, track) in tracks.iter().enumerate() {
+					ensure!(!tracks[..i].contains(track), Error::<T>::InvalidRecoupment);
+					ensure!(album_tracks.contains(track), Error::<T>::TrackNotOnAlbum);
+				}
+			}
+			for src_id in scope.srcs() {
+				<pallet_rmp::Module<T>>::ensure_src_owner(&who, src_id)?;
+				ensure!(Self::recoupments_of(src_id).len() < SRC_MAX_RECOUPMENTS, Error::<T>::TooManyRecoupments);
+			}
+
+			let recoupment_id = Self::next_recoupment_id();
+			for src_id in scope.srcs() {
+				RecoupmentsOf::append(src_id, recoupment_id);
+			}
+			<Recoupments<T>>::insert(recoupment_id, Recoupment {
+				scope,
+				advancer: advancer.clone(),
+				advance,
+				rate,
+				recouped: Zero::zero(),
+			});
+			NextRecoupmentId::put(recoupment_id + 1);
+
+			Self::deposit_event(RawEvent::RecoupmentCreated(who, recoupment_id, advancer, advance));
+			Ok(())
+		}
+
+		/// Accept an asset for royalty payments at `rate`, which advances are recouped from
+		/// its payments at. Calling it again for the asset updates the rate. The pallet account
+		/// must already hold some of the asset, which the assets pallet only allows from its
+		/// minimum balance on, so that claims never reap it.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 2)]
+		pub fn register_asset(origin, asset_id: T::AssetId, rate: AssetRate<BalanceOf<T>, T::Balance>) -> dispatch::DispatchResult {
+			T::AssetOrigin::ensure_origin(origin)?;
+
+			ensure!(!rate.native.is_zero() && !rate.asset.is_zero(), Error::<T>::InvalidAssetRate);
+			ensure!(
+				!<pallet_assets::Module<T>>::balance(asset_id, Self::account_id()).is_zero(),
+				Error::<T>::AssetReserveMissing
+			);
+			<RoyaltyAssets<T>>::insert(asset_id, true);
+			<AssetRates<T>>::insert(asset_id, rate);
+
+			Self::deposit_event(RawEvent::RoyaltyAssetRegistered(asset_id, rate.native, rate.asset));
+			Ok(())
+		}
+
+		/// Pay `amount` of royalties in an accepted asset for an SRC. The payment is split as
+		/// in `pay_royalty`; in push mode, the payouts the assets pallet refuses, such as those
+		/// below the asset's minimum balance, accrue to the payee instead, and royalties paid
+		/// for a suspended SRC are held in an escrow of the asset. Advances are recouped from the
+		/// payment in the asset, valued at the rate of the asset.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(
+			10 + (1 + SPLIT_MAX_NODES as u64) * (SRC_MAX_RECOUPMENTS as u64 + 7 + 3 * ESCROW_MAX_PAYERS as u64),
+			3 + (1 + SPLIT_MAX_NODES as u64) * (SRC_MAX_RECOUPMENTS as u64 * (2 + RECOUPMENT_MAX_TRACKS as u64) + 4 + 3 * ESCROW_MAX_PAYERS as u64),
+		)]
+		#[transactional]
+		pub fn pay_royalty_in_asset(origin, src_id: SrcId, asset_id: T::AssetId, amount: T::Balance) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(Self::is_royalty_asset(asset_id), Error::<T>::AssetNotAccepted);
+			ensure!(<pallet_rmp::Module<T>>::owner_of(&src_id).is_some(), Error::<T>::SrcNotFound);
+
+			// The assets pallet moves the payer's whole balance rather than leave dust behind.
+			let account_id = Self::account_id();
+			let held = <pallet_assets::Module<T>>::balance(asset_id, &account_id);
+			Self::transfer_asset(asset_id, &who, &account_id, amount)?;
+			ensure!(
+				<pallet_assets::Module<T>>::balance(asset_id, &account_id) == held.saturating_add(amount),
+				Error::<T>::PayerBelowMinimum
+			);
+
+			if <pallet_rmp::Module<T>>::is_suspended(&src_id) {
+				return Self::hold_in_asset_escrow(who, src_id, asset_id, amount);
+			}
+			Self::split_royalty_in_asset(who, src_id, asset_id, amount)
+		}
+
+		/// Withdraw the royalties in an asset accrued to the caller for an SRC. Fails while
+		/// they stay below the asset's minimum balance for an account holding none of it.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(4, 4)]
+		#[transactional]
+		pub fn claim_asset(origin, src_id: SrcId, asset_id: T::AssetId) -> dispatch::DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			// Update storage.
-			Something::put(something);
+			let amount = <AccruedAssets<T>>::take(&who, (&src_id, asset_id));
+			ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+			Self::transfer_asset(asset_id, &Self::account_id(), &who, amount)?;
+			<AssetLedgers<T>>::mutate(&src_id, asset_id, |ledger| {
+				ledger.claimed = ledger.claimed.saturating_add(amount);
+				ledger.outstanding = ledger.outstanding.saturating_sub(amount);
+			});
 
-			// Emit an event.
-			Self::deposit_event(RawEvent::SomethingStored(something, who));
-			// Return a successful DispatchResult
+			Self::deposit_event(RawEvent::AssetRoyaltyClaimed(who, src_id, asset_id, amount));
 			Ok(())
 		}
 
-		/// An example dispatchable that may throw a custom error.
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
-		pub fn cause_error(origin) -> dispatch::DispatchResult {
-			let _who = ensure_signed(origin)?;
+		/// Let payouts to the caller below `threshold` accrue until a payout run settles them
+		/// together, once they reach `threshold`. Payout runs settle the royalties the caller
+		/// accrued for SRCs in accrue mode as well. A zero `threshold` opts out of payout runs.
+		/// Thresholds and runs cover the native currency only: payouts in assets are pushed or
+		/// accrued as before, whatever the threshold, and claimed with `claim_asset`.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(3, 4)]
+		pub fn set_payout_threshold(origin, threshold: BalanceOf<T>) -> dispatch::DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			// Read a value from storage.
-			match Something::get() {
-				// Return an error if the value has not been set.
-				None => Err(Error::<T>::NoneValue)?,
-				Some(old) => {
-					// Increment the value read from storage; will error in the event of overflow.
-					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
-					// Update the value in storage with the incremented result.
-					Something::put(new);
-					Ok(())
-				},
+			if threshold.is_zero() {
+				<PayoutThresholds<T>>::remove(&who);
+			} else {
+				<PayoutThresholds<T>>::insert(&who, threshold);
+				if !Self::accrued_total_of(&who).is_zero() {
+					Self::queue_payee(&who);
+				}
 			}
+
+			Self::deposit_event(RawEvent::PayoutThresholdSet(who, threshold));
+			Ok(())
+		}
+
+		/// Set the account receiving the royalties withheld from payees.
+		#[weight = 10_000 + T::DbWeight::get().writes(1)]
+		pub fn set_tax_account(origin, tax_account: T::AccountId) -> dispatch::DispatchResult {
+			T::WithholdingOrigin::ensure_origin(origin)?;
+
+			<TaxAccount<T>>::put(&tax_account);
+
+			Self::deposit_event(RawEvent::TaxAccountSet(tax_account));
+			Ok(())
+		}
+
+		/// Withhold `rate` basis points of the royalties paid to `payee` for the tax account,
+		/// or stop withholding when `rate` is zero.
+		#[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+		pub fn set_withholding_rate(origin, payee: T::AccountId, rate: u32) -> dispatch::DispatchResult {
+			T::WithholdingOrigin::ensure_origin(origin)?;
+
+			ensure!(rate <= MAX_BASIS_POINTS, Error::<T>::InvalidWithholdingRate);
+			if rate.is_zero() {
+				<WithholdingRates<T>>::remove(&payee);
+			} else {
+				ensure!(Self::tax_account().is_some(), Error::<T>::NoTaxAccount);
+				<WithholdingRates<T>>::insert(&payee, rate);
+			}
+
+			Self::deposit_event(RawEvent::WithholdingRateSet(payee, rate));
+			Ok(())
+		}
+	}
+}
+
+/// Outcome of splitting a payment through nested split tables.
+struct Cascade<AccountId, Balance> {
+	payouts: Vec<(AccountId, Balance)>,
+	/// Parts owed to the owners of cleared samples and to advancers, which are not withheld from.
+	exempt: Vec<(AccountId, Balance)>,
+	/// Parts reaching nested SRCs suspended by a takedown notice.
+	escrowed: Vec<(SrcId, Balance)>,
+	/// Distribution entries visited.
+	nodes: usize,
+}
+
+impl<AccountId: PartialEq + Clone, Balance: Saturating + Copy> Cascade<AccountId, Balance> {
+	fn new() -> Self {
+		Self { payouts: Vec::new(), exempt: Vec::new(), escrowed: Vec::new(), nodes: 0 }
+	}
+
+	fn pay(&mut self, account: &AccountId, part: Balance) {
+		Self::add(&mut self.payouts, account, part);
+	}
+
+	fn pay_exempt(&mut self, account: &AccountId, part: Balance) {
+		Self::add(&mut self.exempt, account, part);
+	}
+
+	fn add(payouts: &mut Vec<(AccountId, Balance)>, account: &AccountId, part: Balance) {
+		match payouts.iter_mut().find(|(payee, _)| payee == account) {
+			Some((_, total)) => *total = total.saturating_add(part),
+			None => payouts.push((account.clone(), part)),
+		}
+	}
+}
+
+impl<T: Config> Module<T> {
+	/// Account holding the accrued royalties.
+	pub fn account_id() -> T::AccountId {
+		T::ModuleId::get().into_account()
+	}
+
+	/// Royalties accrued to `payee` and not claimed yet, per SRC.
+	pub fn accrued_of(payee: &T::AccountId) -> Vec<(SrcId, BalanceOf<T>)> {
+		<Accrued<T>>::iter_prefix(payee).collect()
+	}
+
+	/// Royalties in assets accrued to `payee` and not claimed yet, per SRC and asset.
+	pub fn accrued_assets_of(payee: &T::AccountId) -> Vec<(SrcId, T::AssetId, T::Balance)> {
+		<AccruedAssets<T>>::iter_prefix(payee)
+			.map(|((src_id, asset_id), amount)| (src_id, asset_id, amount))
+			.collect()
+	}
+
+	/// Statement period `block` belongs to.
+	pub fn period_of(block: T::BlockNumber) -> T::BlockNumber {
+		block / T::StatementPeriod::get().max(One::one())
+	}
+
+	/// Statement period of the current block.
+	pub fn current_period() -> T::BlockNumber {
+		Self::period_of(<frame_system::Module<T>>::block_number())
+	}
+
+	/// Remove the statements of the periods older than `StatementRetention` periods before
+	/// the current one, `STATEMENT_MAX_PRUNES_PER_BLOCK` payees or empty periods at a time.
+	/// Returns the weight used.
+	fn prune_statements() -> Weight {
+		let current = Self::current_period();
+		let mut period = Self::first_retained_period();
+		let (mut steps, mut reads, mut writes) = (0usize, 1u64, 0u64);
+		while period.saturating_add(T::StatementRetention::get()) < current && steps < STATEMENT_MAX_PRUNES_PER_BLOCK {
+			let payees: Vec<T::AccountId> = <StatementPayees<T>>::iter_prefix(period)
+				.map(|(payee, _)| payee)
+				.take(STATEMENT_MAX_PRUNES_PER_BLOCK - steps)
+				.collect();
+			reads += 1 + payees.len() as u64;
+			if payees.is_empty() {
+				period = period.saturating_add(One::one());
+				<FirstRetainedPeriod<T>>::put(period);
+				steps += 1;
+				writes += 1;
+				continue;
+			}
+			for payee in payees {
+				let key = (payee.clone(), period);
+				<Earnings<T>>::remove_prefix(&key);
+				<AssetEarnings<T>>::remove_prefix(&key);
+				<StatementPayees<T>>::remove(period, &payee);
+				steps += 1;
+				writes += 3;
+			}
+		}
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// Royalties earned by `payee` during `period`, ordered by SRC id. Statements of pruned
+	/// periods are empty.
+	pub fn statement(
+		payee: &T::AccountId,
+		period: T::BlockNumber,
+	) -> Statement<BalanceOf<T>, T::AssetId, T::Balance, T::BlockNumber> {
+		let length = T::StatementPeriod::get().max(One::one());
+		let key = (payee.clone(), period);
+		let mut earnings: Vec<_> = <Earnings<T>>::iter_prefix(&key).collect();
+		earnings.sort_by(|a, b| a.0.cmp(&b.0));
+		let mut asset_earnings: Vec<_> = <AssetEarnings<T>>::iter_prefix(&key)
+			.map(|((src_id, asset_id), amount)| (src_id, asset_id, amount))
+			.collect();
+		asset_earnings.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.encode().cmp(&b.1.encode())));
+		Statement {
+			period,
+			first_block: period.saturating_mul(length),
+			last_block: period.saturating_mul(length).saturating_add(length.saturating_sub(One::one())),
+			earnings,
+			asset_earnings,
+		}
+	}
+
+	/// Replace the distribution table of `src_id` from the current block on, keeping the
+	/// previous ones in its history.
+	fn apply_distributions(src_id: &[u8], distributions: Vec<Distribution<T::AccountId>>) {
+		<Distributions<T>>::insert(src_id, &distributions);
+		<SplitHistory<T>>::append(src_id, SplitRevision {
+			effective_from: <frame_system::Module<T>>::block_number(),
+			distributions,
+		});
+	}
+
+	/// Accounts whose consent a change from `current` to `proposed` needs: those of the payees
+	/// whose share changes, joining and leaving payees included, with the owners standing for
+	/// nested SRCs and split groups.
+	fn affected_approvers(
+		current: &[Distribution<T::AccountId>],
+		proposed: &[Distribution<T::AccountId>],
+	) -> Result<Vec<T::AccountId>, Error<T>> {
+		let mut approvers = Vec::new();
+		let removed_or_changed = current.iter().filter(|d| !proposed.contains(d));
+		let added_or_changed = proposed.iter().filter(|d| !current.contains(d));
+		for distribution in removed_or_changed.chain(added_or_changed) {
+			let approver = Self::payee_account(&distribution.payee)?;
+			if !approvers.contains(&approver) {
+				approvers.push(approver);
+			}
+		}
+		Ok(approvers)
+	}
+
+	/// Cancel the open split change proposal of `src_id`, if any. Returns whether there was one.
+	fn cancel_proposal_of(src_id: &[u8]) -> bool {
+		match ProposalOf::take(src_id) {
+			Some(proposal_id) => {
+				<SplitProposals<T>>::remove(proposal_id);
+				Self::deposit_event(RawEvent::SplitChangeCancelled(proposal_id));
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Open proposal `proposal_id`, checking that `who` may still approve or reject it. The
+	/// owners of nested SRCs and split groups may have changed since it was made; a proposal
+	/// whose approvers are no longer the same is cancelled and `None` returned.
+	fn voting_proposal(
+		who: &T::AccountId,
+		proposal_id: ProposalId,
+	) -> Result<Option<SplitProposal<T::AccountId, T::BlockNumber>>, Error<T>> {
+		let proposal = Self::split_proposal(proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+		let stale = Self::affected_approvers(&Self::distributions_of(&proposal.src_id), &proposal.distributions)
+			.map_or(true, |approvers| approvers != proposal.approvers);
+		if stale {
+			<SplitProposals<T>>::remove(proposal_id);
+			ProposalOf::remove(&proposal.src_id);
+			Self::deposit_event(RawEvent::SplitChangeCancelled(proposal_id));
+			return Ok(None);
+		}
+		ensure!(proposal.approvers.contains(who), Error::<T>::NotApprover);
+		ensure!(
+			!proposal.approvals.contains(who) && !proposal.rejections.contains(who),
+			Error::<T>::AlreadyVoted
+		);
+		Ok(Some(proposal))
+	}
+
+	/// Whether `votes` out of `approvers` reach `ApprovalThreshold`.
+	fn threshold_reached(votes: usize, approvers: usize) -> bool {
+		let threshold = T::ApprovalThreshold::get().min(MAX_BASIS_POINTS);
+		votes as u64 * MAX_BASIS_POINTS as u64 >= threshold as u64 * approvers as u64
+	}
+
+	fn is_approved(proposal: &SplitProposal<T::AccountId, T::BlockNumber>) -> bool {
+		Self::threshold_reached(proposal.approvals.len(), proposal.approvers.len())
+	}
+
+	/// Replace the distribution table of the SRC of an approved proposal and close it. The
+	/// nested tables may have changed since the proposal, so its nesting is checked again.
+	fn enact(proposal_id: ProposalId, proposal: SplitProposal<T::AccountId, T::BlockNumber>) -> dispatch::DispatchResult {
+		let src_id = proposal.src_id;
+		Self::validate_nesting(Payee::Src(src_id.clone()), &proposal.distributions)?;
+		<SplitProposals<T>>::remove(proposal_id);
+		ProposalOf::remove(&src_id);
+		Self::apply_distributions(&src_id, proposal.distributions);
+
+		Self::deposit_event(RawEvent::SplitChangeEnacted(proposal_id, src_id));
+		Ok(())
+	}
+
+	/// Credit `share` of the royalties of `src_id` to `payee`, queueing the payee for the
+	/// payout runs if they set a payout threshold.
+	fn credit(payee: &T::AccountId, src_id: &[u8], share: BalanceOf<T>) {
+		<Accrued<T>>::mutate(payee, src_id, |accrued| *accrued = accrued.saturating_add(share));
+		<AccruedTotals<T>>::mutate(payee, |total| *total = total.saturating_add(share));
+		if !Self::payout_threshold_of(payee).is_zero() {
+			Self::queue_payee(payee);
+		}
+	}
+
+	fn queue_payee(payee: &T::AccountId) {
+		if !Self::in_payout_queue(payee) {
+			<InPayoutQueue<T>>::insert(payee, true);
+			Self::enqueue_payout(payee.clone());
+		}
+	}
+
+	fn enqueue_payout(payee: T::AccountId) {
+		let tail = Self::payout_queue_tail();
+		<PayoutQueue<T>>::insert(tail, payee);
+		PayoutQueueTail::put(tail + 1);
+	}
+
+	/// Worst-case weight of settling one step of a payee in a payout run.
+	fn payout_step_weight() -> Weight {
+		T::DbWeight::get().reads_writes(4 + 4 * PAYOUT_MAX_SRCS as u64, 4 + 4 * PAYOUT_MAX_SRCS as u64)
+	}
+
+	/// Settle the payees of the payout run in progress within `max_weight`, paying those whose
+	/// accrued royalties reached their payout threshold and moving the others to the back of
+	/// the queue. A payee is settled `PAYOUT_MAX_SRCS` SRCs at a time, in one transfer; a payee
+	/// the transfer fails for leaves the queue until their next royalties are credited, rather
+	/// than hold up every run. Returns the weight used.
+	fn run_payouts(max_weight: Weight) -> Weight {
+		let run_end = match Self::payout_run_end() {
+			Some(run_end) => run_end,
+			None => return T::DbWeight::get().reads(1),
+		};
+		let step_weight = Self::payout_step_weight();
+		let mut weight = T::DbWeight::get().reads_writes(2, 2);
+		let account_id = Self::account_id();
+		let mut head = Self::payout_queue_head();
+		while head < run_end && weight.saturating_add(step_weight) <= max_weight {
+			weight = weight.saturating_add(step_weight);
+			let payee = match <PayoutQueue<T>>::get(head) {
+				Some(payee) => payee,
+				None => {
+					head += 1;
+					continue;
+				}
+			};
+			let total = Self::accrued_total_of(&payee);
+			let threshold = Self::payout_threshold_of(&payee);
+			if total.is_zero() || threshold.is_zero() {
+				<PayoutQueue<T>>::remove(head);
+				<InPayoutQueue<T>>::remove(&payee);
+				head += 1;
+				continue;
+			}
+			if total < threshold {
+				<PayoutQueue<T>>::remove(head);
+				Self::enqueue_payout(payee);
+				head += 1;
+				continue;
+			}
+
+			let accrued: Vec<_> = <Accrued<T>>::iter_prefix(&payee).take(PAYOUT_MAX_SRCS).collect();
+			let settled = accrued.iter().fold(Zero::zero(), |total: BalanceOf<T>, (_, amount)| total.saturating_add(*amount));
+			if NativeCurrency::<T>::transfer(&account_id, &payee, settled, ExistenceRequirement::KeepAlive).is_err() {
+				<PayoutQueue<T>>::remove(head);
+				<InPayoutQueue<T>>::remove(&payee);
+				head += 1;
+				Self::deposit_event(RawEvent::PayoutFailed(payee, settled));
+				continue;
+			}
+			for (src_id, amount) in &accrued {
+				<Accrued<T>>::remove(&payee, src_id);
+				<Ledgers<T>>::mutate(src_id, |ledger| {
+					ledger.claimed = ledger.claimed.saturating_add(*amount);
+					ledger.outstanding = ledger.outstanding.saturating_sub(*amount);
+				});
+			}
+			let remaining = total.saturating_sub(settled);
+			if !settled.is_zero() {
+				Self::deposit_event(RawEvent::PayoutSettled(payee.clone(), settled));
+			}
+			if remaining.is_zero() {
+				<AccruedTotals<T>>::remove(&payee);
+				<PayoutQueue<T>>::remove(head);
+				<InPayoutQueue<T>>::remove(&payee);
+				head += 1;
+				continue;
+			}
+			<AccruedTotals<T>>::insert(&payee, remaining);
+			if accrued.len() < PAYOUT_MAX_SRCS {
+				// Accrued while being settled: left for the next run.
+				<PayoutQueue<T>>::remove(head);
+				Self::enqueue_payout(payee);
+				head += 1;
+			}
+		}
+		PayoutQueueHead::put(head);
+		if head >= run_end {
+			PayoutRunEnd::kill();
+			Self::deposit_event(RawEvent::PayoutRunCompleted);
+		}
+		weight
+	}
+
+	/// Deduct the withholding rate of each payee from their payout and pay the withheld
+	/// amounts to the tax account. The `exempt` payouts of sample owners and advancers are
+	/// made in full. Returns the payouts to make and the earnings of each payee.
+	fn withhold<Balance: AtLeast32BitUnsigned + Copy>(
+		payouts: Vec<(T::AccountId, Balance)>,
+		exempt: Vec<(T::AccountId, Balance)>,
+	) -> (Vec<(T::AccountId, Balance)>, Vec<(T::AccountId, Earning<Balance>)>) {
+		let tax_account = Self::tax_account();
+		let mut earnings = Vec::with_capacity(exempt.len() + payouts.len());
+		let mut net_payouts = Vec::with_capacity(exempt.len() + payouts.len() + 1);
+		for (payee, gross) in exempt {
+			earnings.push((payee.clone(), Earning { gross, withheld: Zero::zero() }));
+			net_payouts.push((payee, gross));
+		}
+		let mut withheld_total = Balance::zero();
+		for (payee, gross) in payouts {
+			let rate = if tax_account.is_some() { Self::withholding_rate_of(&payee) } else { 0 };
+			let withheld = if rate.is_zero() {
+				Zero::zero()
+			} else {
+				split_by_basis_points(gross, &[rate, MAX_BASIS_POINTS - rate])[0]
+			};
+			withheld_total = withheld_total.saturating_add(withheld);
+			earnings.push((payee.clone(), Earning { gross, withheld }));
+			net_payouts.push((payee, gross - withheld));
+		}
+		if let Some(tax_account) = tax_account.filter(|_| !withheld_total.is_zero()) {
+			match net_payouts.iter_mut().find(|(payee, _)| payee == &tax_account) {
+				Some((_, total)) => *total = total.saturating_add(withheld_total),
+				None => net_payouts.push((tax_account, withheld_total)),
+			}
+		}
+		(net_payouts, earnings)
+	}
+
+	/// Add the earnings of the payees of a payment for `src_id` to their statements of the
+	/// current period.
+	fn record_earnings(src_id: &[u8], earnings: &[(T::AccountId, Earning<BalanceOf<T>>)]) {
+		let period = Self::current_period();
+		for (payee, earning) in earnings.iter().filter(|(_, earning)| !earning.gross.is_zero()) {
+			<Earnings<T>>::mutate((payee.clone(), period), src_id, |earned| {
+				earned.gross = earned.gross.saturating_add(earning.gross);
+				earned.withheld = earned.withheld.saturating_add(earning.withheld);
+			});
+			<StatementPayees<T>>::insert(period, payee, true);
+		}
+	}
+
+	/// Add the earnings of the payees of a payment in an asset for `src_id` to their
+	/// statements of the current period.
+	fn record_asset_earnings(src_id: &[u8], asset_id: T::AssetId, earnings: &[(T::AccountId, Earning<T::Balance>)]) {
+		let period = Self::current_period();
+		for (payee, earning) in earnings.iter().filter(|(_, earning)| !earning.gross.is_zero()) {
+			<AssetEarnings<T>>::mutate((payee.clone(), period), (src_id, asset_id), |earned| {
+				earned.gross = earned.gross.saturating_add(earning.gross);
+				earned.withheld = earned.withheld.saturating_add(earning.withheld);
+			});
+			<StatementPayees<T>>::insert(period, payee, true);
+		}
+	}
+
+	/// Split `amount` paid by `who` for `src_id`, which is not suspended, as `pay_royalty`
+	/// describes. An escrow being released is paid out by the pallet account itself, and
+	/// then no nested SRC may be suspended.
+	fn split_royalty(who: T::AccountId, src_id: SrcId, amount: BalanceOf<T>) -> dispatch::DispatchResult {
+		let distributions = Self::distributions_of(&src_id);
+		ensure!(!distributions.is_empty(), Error::<T>::NoDistributions);
+
+		let (mut exempt, rest) = Self::sample_payouts(&src_id, amount);
+		let recouped = Self::recoup(&src_id, rest);
+		let rest = recouped.iter().fold(rest, |rest, (_, part)| rest.saturating_sub(*part));
+		exempt.extend(recouped);
+		let cascade = Self::split_payouts(&src_id, rest, &distributions, &mut |src_id: &[u8], part: BalanceOf<T>| {
+			Ok(Self::recoup(src_id, part))
+		})?;
+		exempt.extend(cascade.exempt);
+		// Parts held for suspended nested SRCs are accounted for by their escrow.
+		ensure!(who != Self::account_id() || cascade.escrowed.is_empty(), Error::<T>::SrcSuspended);
+		let mut amount = amount;
+		for (nested_src_id, part) in cascade.escrowed {
+			Self::hold_in_escrow(who.clone(), nested_src_id, part)?;
+			amount = amount.saturating_sub(part);
+		}
+		let (payouts, earnings) = Self::withhold(cascade.payouts, exempt);
+		Self::record_earnings(&src_id, &earnings);
+		match Self::payout_mode_of(&src_id) {
+			PayoutMode::Push => {
+				let (mut pushed, mut accrued) = (Vec::new(), Vec::new());
+				for (payee, share) in payouts {
+					if payee == who || share.is_zero() {
+						pushed.push((payee, share));
+					} else if share < Self::payout_threshold_of(&payee)
+						|| NativeCurrency::<T>::total_balance(&payee).saturating_add(share) < NativeCurrency::<T>::minimum_balance()
+					{
+						Self::credit(&payee, &src_id, share);
+						accrued.push((payee, share));
+					} else {
+						NativeCurrency::<T>::transfer(&who, &payee, share, ExistenceRequirement::KeepAlive)?;
+						pushed.push((payee, share));
+					}
+				}
+				let accrued_total = accrued.iter().fold(Zero::zero(), |total: BalanceOf<T>, (_, share)| total.saturating_add(*share));
+				if !accrued_total.is_zero() {
+					NativeCurrency::<T>::transfer(&who, &Self::account_id(), accrued_total, ExistenceRequirement::KeepAlive)?;
+				}
+				<Ledgers<T>>::mutate(&src_id, |ledger| {
+					ledger.paid = ledger.paid.saturating_add(amount);
+					ledger.claimed = ledger.claimed.saturating_add(amount.saturating_sub(accrued_total));
+					ledger.outstanding = ledger.outstanding.saturating_add(accrued_total);
+				});
+				if !pushed.is_empty() {
+					let pushed_total = amount.saturating_sub(accrued_total);
+					Self::deposit_event(RawEvent::RoyaltySplit(who.clone(), src_id.clone(), pushed_total, pushed));
+				}
+				if !accrued.is_empty() {
+					Self::deposit_event(RawEvent::RoyaltyAccrued(who, src_id, accrued_total, accrued));
+				}
+			}
+			PayoutMode::Accrue => {
+				NativeCurrency::<T>::transfer(&who, &Self::account_id(), amount, ExistenceRequirement::KeepAlive)?;
+				for (payee, share) in &payouts {
+					if !share.is_zero() {
+						Self::credit(payee, &src_id, *share);
+					}
+				}
+				<Ledgers<T>>::mutate(&src_id, |ledger| {
+					ledger.paid = ledger.paid.saturating_add(amount);
+					ledger.outstanding = ledger.outstanding.saturating_add(amount);
+				});
+				Self::deposit_event(RawEvent::RoyaltyAccrued(who, src_id, amount, payouts));
+			}
+		}
+		Ok(())
+	}
+
+	/// Split `amount` of an asset paid by `who` for `src_id`, which is not suspended, as
+	/// `pay_royalty_in_asset` describes. The payment is already held by the pallet account;
+	/// an escrow being released is paid out with `who` being the pallet account, and then no
+	/// nested SRC may be suspended.
+	fn split_royalty_in_asset(
+		who: T::AccountId,
+		src_id: SrcId,
+		asset_id: T::AssetId,
+		amount: T::Balance,
+	) -> dispatch::DispatchResult {
+		let distributions = Self::distributions_of(&src_id);
+		ensure!(!distributions.is_empty(), Error::<T>::NoDistributions);
+
+		let account_id = Self::account_id();
+		let (mut exempt, rest) = Self::sample_payouts(&src_id, amount);
+		let recouped = Self::recoup_in_asset(&src_id, asset_id, rest);
+		let rest = recouped.iter().fold(rest, |rest, (_, part)| rest.saturating_sub(*part));
+		exempt.extend(recouped);
+		let cascade = Self::split_payouts(&src_id, rest, &distributions, &mut |src_id: &[u8], part: T::Balance| {
+			Ok(Self::recoup_in_asset(src_id, asset_id, part))
+		})?;
+		exempt.extend(cascade.exempt);
+		ensure!(who != account_id || cascade.escrowed.is_empty(), Error::<T>::SrcSuspended);
+		let mut amount = amount;
+		for (nested_src_id, part) in cascade.escrowed {
+			Self::hold_in_asset_escrow(who.clone(), nested_src_id, asset_id, part)?;
+			amount = amount.saturating_sub(part);
+		}
+		let (payouts, earnings) = Self::withhold(cascade.payouts, exempt);
+		Self::record_asset_earnings(&src_id, asset_id, &earnings);
+		let push = Self::payout_mode_of(&src_id) == PayoutMode::Push;
+		let (mut pushed, mut accrued) = (Vec::new(), Vec::new());
+		for (payee, share) in payouts {
+			if share.is_zero() {
+				continue;
+			}
+			if push && Self::try_transfer_asset(asset_id, &account_id, &payee, share) {
+				pushed.push((payee, share));
+			} else {
+				<AccruedAssets<T>>::mutate(&payee, (&src_id, asset_id), |total| *total = total.saturating_add(share));
+				accrued.push((payee, share));
+			}
+		}
+
+		let pushed_total = pushed.iter().fold(Zero::zero(), |total: T::Balance, (_, share)| total.saturating_add(*share));
+		let accrued_total = amount.saturating_sub(pushed_total);
+		<AssetLedgers<T>>::mutate(&src_id, asset_id, |ledger| {
+			ledger.paid = ledger.paid.saturating_add(amount);
+			ledger.claimed = ledger.claimed.saturating_add(pushed_total);
+			ledger.outstanding = ledger.outstanding.saturating_add(accrued_total);
+		});
+		if !pushed.is_empty() {
+			Self::deposit_event(RawEvent::AssetRoyaltySplit(who.clone(), src_id.clone(), asset_id, pushed_total, pushed));
+		}
+		if !accrued.is_empty() {
+			Self::deposit_event(RawEvent::AssetRoyaltyAccrued(who, src_id, asset_id, accrued_total, accrued));
+		}
+		Ok(())
+	}
+
+	/// Hold `amount` paid for a suspended SRC in escrow. The escrow opened by the first
+	/// payment expires `EscrowPeriod` blocks later; a payment after that refunds it and
+	/// opens a new one.
+	fn hold_in_escrow(payer: T::AccountId, src_id: SrcId, amount: BalanceOf<T>) -> dispatch::DispatchResult {
+		let account_id = Self::account_id();
+		let (mut escrow, refunded) = Self::current_escrow(Self::escrow_of(&src_id), |depositor, deposit| {
+			Self::try_transfer(&account_id, depositor, deposit)
+		});
+		if !refunded.is_zero() {
+			Self::deposit_event(RawEvent::EscrowRefunded(src_id.clone(), refunded));
+		}
+		Self::add_deposit(&mut escrow, &payer, amount)?;
+		NativeCurrency::<T>::transfer(&payer, &account_id, amount, ExistenceRequirement::KeepAlive)?;
+
+		let expires = escrow.expires;
+		<Escrows<T>>::insert(&src_id, escrow);
+		Self::deposit_event(RawEvent::RoyaltyEscrowed(payer, src_id, amount, expires));
+		Ok(())
+	}
+
+	/// Hold `amount` of an asset, already moved to the pallet account, in escrow for a
+	/// suspended SRC, as `hold_in_escrow` does.
+	fn hold_in_asset_escrow(
+		payer: T::AccountId,
+		src_id: SrcId,
+		asset_id: T::AssetId,
+		amount: T::Balance,
+	) -> dispatch::DispatchResult {
+		let account_id = Self::account_id();
+		let (mut escrow, refunded) = Self::current_escrow(Self::asset_escrow_of(&src_id, asset_id), |depositor, deposit| {
+			Self::try_transfer_asset(asset_id, &account_id, depositor, deposit)
+		});
+		if !refunded.is_zero() {
+			Self::deposit_event(RawEvent::AssetEscrowRefunded(src_id.clone(), asset_id, refunded));
+		}
+		Self::add_deposit(&mut escrow, &payer, amount)?;
+
+		let expires = escrow.expires;
+		<AssetEscrows<T>>::insert(&src_id, asset_id, escrow);
+		Self::deposit_event(RawEvent::AssetRoyaltyEscrowed(payer, src_id, asset_id, amount, expires));
+		Ok(())
+	}
+
+	/// Escrow taking new payments: `escrow` until it expires, then a new one. The payers of
+	/// an expired escrow are refunded with `refund`, and the deposits it fails to refund are
+	/// kept in the new escrow. Returns the escrow and the amount refunded.
+	fn current_escrow<Balance: AtLeast32BitUnsigned + Copy>(
+		escrow: Option<Escrow<T::AccountId, Balance, T::BlockNumber>>,
+		mut refund: impl FnMut(&T::AccountId, Balance) -> bool,
+	) -> (Escrow<T::AccountId, Balance, T::BlockNumber>, Balance) {
+		let now = <frame_system::Module<T>>::block_number();
+		let (deposits, refunded) = match escrow {
+			Some(escrow) if now < escrow.expires => return (escrow, Balance::zero()),
+			Some(escrow) => Self::refund_deposits(escrow.deposits, refund),
+			None => (Vec::new(), Balance::zero()),
+		};
+		let total = deposits.iter().fold(Balance::zero(), |total, (_, deposit)| total.saturating_add(*deposit));
+		(Escrow { total, deposits, expires: now.saturating_add(T::EscrowPeriod::get()) }, refunded)
+	}
+
+	/// Refund each of `deposits` with `refund`. Returns the deposits it failed to refund and
+	/// the amount refunded.
+	fn refund_deposits<Balance: AtLeast32BitUnsigned + Copy>(
+		deposits: Vec<(T::AccountId, Balance)>,
+		mut refund: impl FnMut(&T::AccountId, Balance) -> bool,
+	) -> (Vec<(T::AccountId, Balance)>, Balance) {
+		let mut refunded = Balance::zero();
+		let kept = deposits.into_iter()
+			.filter(|(depositor, deposit)| {
+				let done = refund(depositor, *deposit);
+				if done {
+					refunded = refunded.saturating_add(*deposit);
+				}
+				!done
+			})
+			.collect();
+		(kept, refunded)
+	}
+
+	/// Escrow left with the deposits an expired escrow failed to refund, if any.
+	fn remaining_escrow<Balance: AtLeast32BitUnsigned + Copy>(
+		deposits: Vec<(T::AccountId, Balance)>,
+		expires: T::BlockNumber,
+	) -> Option<Escrow<T::AccountId, Balance, T::BlockNumber>> {
+		if deposits.is_empty() {
+			return None;
+		}
+		let total = deposits.iter().fold(Balance::zero(), |total, (_, deposit)| total.saturating_add(*deposit));
+		Some(Escrow { total, deposits, expires })
+	}
+
+	/// Add `amount` paid by `payer` to `escrow`.
+	fn add_deposit<Balance: AtLeast32BitUnsigned + Copy>(
+		escrow: &mut Escrow<T::AccountId, Balance, T::BlockNumber>,
+		payer: &T::AccountId,
+		amount: Balance,
+	) -> Result<(), Error<T>> {
+		match escrow.deposits.iter_mut().find(|(depositor, _)| depositor == payer) {
+			Some((_, deposit)) => *deposit = deposit.saturating_add(amount),
+			None => {
+				ensure!(escrow.deposits.len() < ESCROW_MAX_PAYERS, Error::<T>::EscrowFull);
+				escrow.deposits.push((payer.clone(), amount));
+			}
+		}
+		escrow.total = escrow.total.saturating_add(amount);
+		Ok(())
+	}
+
+	/// Pay down the advances recouped from the royalties of `src_id`, oldest agreement
+	/// first, and return the part of `amount` owed to each advancer.
+	fn recoup(src_id: &[u8], amount: BalanceOf<T>) -> Vec<(T::AccountId, BalanceOf<T>)> {
+		Self::recoup_with(src_id, amount, |part| part, |value| value, RawEvent::AdvanceRecouped)
+	}
+
+	/// Pay down the advances recouped from the royalties of `src_id` with `amount` of an
+	/// asset, valued at the rate of the asset, as `recoup` does.
+	fn recoup_in_asset(src_id: &[u8], asset_id: T::AssetId, amount: T::Balance) -> Vec<(T::AccountId, T::Balance)> {
+		let rate = match Self::asset_rate_of(asset_id) {
+			Some(rate) => rate,
+			None => return Vec::new(),
+		};
+		Self::recoup_with(
+			src_id,
+			amount,
+			|part| rate.value_of(part),
+			|value| rate.cost_of(value),
+			|recoupment_id, part, unrecouped| RawEvent::AdvanceRecoupedInAsset(recoupment_id, asset_id, part, unrecouped),
+		)
+	}
+
+	/// Pay down the advances of `src_id` with `amount`, whose parts are worth `value_of` them
+	/// in the native currency the advances are kept in. `cost_of` gives the smallest part
+	/// worth a native amount, so that no agreement takes more than its unrecouped balance.
+	fn recoup_with<Balance: AtLeast32BitUnsigned + Copy>(
+		src_id: &[u8],
+		amount: Balance,
+		value_of: impl Fn(Balance) -> BalanceOf<T>,
+		cost_of: impl Fn(BalanceOf<T>) -> Balance,
+		event: impl Fn(RecoupmentId, Balance, BalanceOf<T>) -> Event<T>,
+	) -> Vec<(T::AccountId, Balance)> {
+		let mut remaining = amount;
+		let mut recouped = Vec::new();
+		for recoupment_id in Self::recoupments_of(src_id) {
+			let mut recoupment = match Self::recoupment(recoupment_id) {
+				Some(recoupment) => recoupment,
+				None => continue,
+			};
+			let part = split_by_basis_points(remaining, &[recoupment.rate, MAX_BASIS_POINTS - recoupment.rate])[0]
+				.min(cost_of(recoupment.unrecouped()));
+			// Parts worth nothing in the native currency would never pay the advance down.
+			let value = value_of(part);
+			if value.is_zero() {
+				continue;
+			}
+			recoupment.recouped = recoupment.recouped.saturating_add(value).min(recoupment.advance);
+			remaining = remaining.saturating_sub(part);
+			let unrecouped = recoupment.unrecouped();
+			if unrecouped.is_zero() {
+				for track in recoupment.scope.srcs() {
+					RecoupmentsOf::mutate(track, |ids| ids.retain(|id| *id != recoupment_id));
+				}
+			}
+			recouped.push((recoupment.advancer.clone(), part));
+			<Recoupments<T>>::insert(recoupment_id, recoupment);
+			Self::deposit_event(event(recoupment_id, part, unrecouped));
+		}
+		recouped
+	}
+
+	/// Transfer `amount` of the native currency, keeping `from` alive. Returns whether it
+	/// was transferred.
+	fn try_transfer(from: &T::AccountId, to: &T::AccountId, amount: BalanceOf<T>) -> bool {
+		with_transaction(|| match NativeCurrency::<T>::transfer(from, to, amount, ExistenceRequirement::KeepAlive) {
+			Ok(()) => TransactionOutcome::Commit(true),
+			Err(_) => TransactionOutcome::Rollback(false),
+		})
+	}
+
+	/// Transfer `amount` of an asset as `transfer_asset` does, leaving no trace of the
+	/// attempt when the assets pallet refuses it. Returns whether it was transferred.
+	fn try_transfer_asset(asset_id: T::AssetId, from: &T::AccountId, to: &T::AccountId, amount: T::Balance) -> bool {
+		with_transaction(|| match Self::transfer_asset(asset_id, from, to, amount) {
+			Ok(()) => TransactionOutcome::Commit(true),
+			Err(_) => TransactionOutcome::Rollback(false),
+		})
+	}
+
+	/// Transfer `amount` of an asset through the assets pallet, as signed by `from`.
+	fn transfer_asset(
+		asset_id: T::AssetId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: T::Balance,
+	) -> dispatch::DispatchResult {
+		pallet_assets::Call::<T>::transfer(asset_id, T::Lookup::unlookup(to.clone()), amount)
+			.dispatch_bypass_filter(RawOrigin::Signed(from.clone()).into())
+			.map(|_| ())
+			.map_err(|e| e.error)
+	}
+
+	pub fn validate_distributions(distributions: &[Distribution<T::AccountId>]) -> Result<(), Error<T>> {
+		ensure!(
+			!distributions.is_empty() && distributions.len() <= SRC_MAX_PAYEES,
+			Error::<T>::InvalidPayeeCount
+		);
+		ensure!(distributions.iter().all(|d| d.bp > 0), Error::<T>::InvalidShares);
+		ensure!(
+			distributions.iter().map(|d| d.bp).sum::<u32>() == MAX_BASIS_POINTS,
+			Error::<T>::InvalidShares
+		);
+		for (i, distribution) in distributions.iter().enumerate() {
+			ensure!(
+				distributions[..i].iter().all(|other| other.payee != distribution.payee),
+				Error::<T>::DuplicatePayee
+			);
+		}
+		Ok(())
+	}
+
+	/// Check that nested split tables referenced by the distribution table of `root` exist,
+	/// never lead back to `root` and stay within `SPLIT_MAX_DEPTH` and `SPLIT_MAX_NODES`.
+	pub fn validate_nesting(
+		root: Payee<T::AccountId>,
+		distributions: &[Distribution<T::AccountId>],
+	) -> Result<(), Error<T>> {
+		let mut path = sp_std::vec![root];
+		Self::expand(&mut path, distributions, 0u32, false, &mut |_, _| Ok(Vec::new()), &mut Cascade::new())
+	}
+
+	/// Distribution table a nested payee splits its share with.
+	fn nested_distributions(payee: &Payee<T::AccountId>) -> Result<Vec<Distribution<T::AccountId>>, Error<T>> {
+		match payee {
+			Payee::Account(account) => Ok(sp_std::vec![Distribution::new(account.clone(), MAX_BASIS_POINTS)]),
+			Payee::Src(src_id) => {
+				let distributions = Self::distributions_of(src_id);
+				if !distributions.is_empty() {
+					return Ok(distributions);
+				}
+				let owner = <pallet_rmp::Module<T>>::owner_of(src_id).ok_or(Error::<T>::SrcNotFound)?;
+				Ok(sp_std::vec![Distribution::new(owner, MAX_BASIS_POINTS)])
+			}
+			Payee::Group(split_group_id) => Self::split_group(split_group_id)
+				.map(|split_group| split_group.distributions)
+				.ok_or(Error::<T>::SplitGroupNotFound),
+		}
+	}
+
+	/// Account standing for a payee: the account itself, or the owner of a nested SRC or
+	/// split group.
+	fn payee_account(payee: &Payee<T::AccountId>) -> Result<T::AccountId, Error<T>> {
+		match payee {
+			Payee::Account(account) => Ok(account.clone()),
+			Payee::Src(src_id) => <pallet_rmp::Module<T>>::owner_of(src_id).ok_or(Error::<T>::SrcNotFound),
+			Payee::Group(split_group_id) => Self::split_group(split_group_id)
+				.map(|split_group| split_group.owner)
+				.ok_or(Error::<T>::SplitGroupNotFound),
+		}
+	}
+
+	/// Split `amount` along `distributions` and the split tables nested in them, adding the
+	/// part of each account to `cascade`. `path` holds the tables being expanded, outermost
+	/// first.
+	///
+	/// When `paying`, a nested SRC is paid as if its royalties were paid directly: its part is
+	/// escrowed while it is suspended, otherwise its cleared samples get their share and
+	/// `recoup` takes the advances out of the rest. Nested payees whose part is zero are
+	/// skipped, and a nested table that would lead back to one being expanded or exceed
+	/// `SPLIT_MAX_DEPTH` or `SPLIT_MAX_NODES` is not expanded: the tables around it may have
+	/// changed since it was checked, so its owner gets the whole part instead.
+	fn expand<Balance: AtLeast32BitUnsigned + Copy>(
+		path: &mut Vec<Payee<T::AccountId>>,
+		distributions: &[Distribution<T::AccountId>],
+		amount: Balance,
+		paying: bool,
+		recoup: &mut dyn FnMut(&[u8], Balance) -> Result<Vec<(T::AccountId, Balance)>, Error<T>>,
+		cascade: &mut Cascade<T::AccountId, Balance>,
+	) -> Result<(), Error<T>> {
+		cascade.nodes += distributions.len();
+		ensure!(cascade.nodes <= SPLIT_MAX_NODES, Error::<T>::SplitTooLarge);
+
+		let bps: Vec<u32> = distributions.iter().map(|d| d.bp).collect();
+		for (distribution, part) in distributions.iter().zip(split_by_basis_points(amount, &bps)) {
+			let nested = match &distribution.payee {
+				Payee::Account(account) => {
+					cascade.pay(account, part);
+					continue;
+				}
+				nested => nested,
+			};
+			if !paying {
+				ensure!(!path.contains(nested), Error::<T>::SplitCycle);
+				ensure!(path.len() < SPLIT_MAX_DEPTH, Error::<T>::SplitTooDeep);
+				let nested_distributions = Self::nested_distributions(nested)?;
+				path.push(nested.clone());
+				Self::expand(path, &nested_distributions, part, paying, recoup, cascade)?;
+				path.pop();
+				continue;
+			}
+
+			if part.is_zero() {
+				continue;
+			}
+			let mut part = part;
+			if let Payee::Src(src_id) = nested {
+				if <pallet_rmp::Module<T>>::is_suspended(src_id) {
+					cascade.escrowed.push((src_id.clone(), part));
+					continue;
+				}
+				let (samples, own) = Self::sample_payouts(src_id, part);
+				let recouped = recoup(src_id, own)?;
+				part = recouped.iter().fold(own, |rest, (_, recouped)| rest.saturating_sub(*recouped));
+				for (account, share) in samples.into_iter().chain(recouped) {
+					cascade.pay_exempt(&account, share);
+				}
+			}
+			let nested_distributions = Self::nested_distributions(nested)?;
+			if path.contains(nested)
+				|| path.len() >= SPLIT_MAX_DEPTH
+				|| cascade.nodes + nested_distributions.len() > SPLIT_MAX_NODES
+			{
+				cascade.pay(&Self::payee_account(nested)?, part);
+				continue;
+			}
+			path.push(nested.clone());
+			Self::expand(path, &nested_distributions, part, paying, recoup, cascade)?;
+			path.pop();
+		}
+		Ok(())
+	}
+
+	/// Shares of the owners of the cleared samples of `src_id` when `amount` is paid for it,
+	/// and the part left to the SRC itself.
+	fn sample_payouts<Balance: AtLeast32BitUnsigned + Copy>(
+		src_id: &[u8],
+		amount: Balance,
+	) -> (Vec<(T::AccountId, Balance)>, Balance) {
+		let samples = <pallet_rmp::Module<T>>::cleared_sample_shares(src_id);
+		let mut shares: Vec<u32> = samples.iter().map(|(_, bp)| *bp).collect();
+		shares.push(MAX_BASIS_POINTS.saturating_sub(shares.iter().sum()));
+		let mut parts = split_by_basis_points(amount, &shares);
+		let mut own = parts.pop().unwrap_or_else(Zero::zero);
+
+		let mut payouts = Vec::with_capacity(samples.len());
+		for ((sampled_src_id, _), part) in samples.iter().zip(parts) {
+			match <pallet_rmp::Module<T>>::owner_of(sampled_src_id) {
+				Some(owner) => payouts.push((owner, part)),
+				None => own = own.saturating_add(part),
+			}
+		}
+		(payouts, own)
+	}
+
+	/// Split `amount` among the payees of `distributions`, the table of `src_id`, in table
+	/// order, nested split tables being expanded in place as in `expand`.
+	fn split_payouts<Balance: AtLeast32BitUnsigned + Copy>(
+		src_id: &[u8],
+		amount: Balance,
+		distributions: &[Distribution<T::AccountId>],
+		recoup: &mut dyn FnMut(&[u8], Balance) -> Result<Vec<(T::AccountId, Balance)>, Error<T>>,
+	) -> Result<Cascade<T::AccountId, Balance>, Error<T>> {
+		let mut path = sp_std::vec![Payee::Src(src_id.to_vec())];
+		let mut cascade = Cascade::new();
+		Self::expand(&mut path, distributions, amount, true, recoup, &mut cascade)?;
+		Ok(cascade)
+	}
+}
+
+impl<T: Config> pallet_rmp::OnRightsChange<T::AccountId> for Module<T> {
+	/// Cancels the open split change proposal of the previous owner.
+	fn on_granted(src_id: &[u8], _grantor: &T::AccountId, _grantee: &T::AccountId) -> Weight {
+		let cancelled = Self::cancel_proposal_of(src_id);
+		T::DbWeight::get().reads_writes(1, 2 * cancelled as u64)
+	}
+
+	/// Cancels the open split change proposal of the grantee.
+	fn on_reverted(src_id: &[u8], _grantee: &T::AccountId, _grantor: &T::AccountId) -> Weight {
+		let cancelled = Self::cancel_proposal_of(src_id);
+		T::DbWeight::get().reads_writes(1, 2 * cancelled as u64)
+	}
+
+	fn max_weight() -> Weight {
+		T::DbWeight::get().reads_writes(1, 2)
+	}
+}
+
+impl<T: Config> pallet_rmp::OnTakedownSettled<T::AccountId> for Module<T> {
+	/// Records who the escrows of the SRC go to: the claimant of an upheld notice, or the
+	/// payees of the SRC.
+	fn on_settled(src_id: &[u8], claimant: &T::AccountId, upheld: bool) -> Weight {
+		let beneficiary = if upheld {
+			EscrowBeneficiary::Claimant(claimant.clone())
+		} else {
+			EscrowBeneficiary::Payees
+		};
+		<EscrowBeneficiaries<T>>::insert(src_id, beneficiary);
+		T::DbWeight::get().writes(1)
+	}
+
+	fn max_weight() -> Weight {
+		T::DbWeight::get().writes(1)
+	}
+}
+
+/// Splits `amount` into parts proportional to `shares`, in basis points adding up to
+/// `MAX_BASIS_POINTS`. Each part is rounded down and the units lost to rounding go one
+/// by one to the parts with the largest remainders, the earliest share winning ties, so
+/// the parts always add up to `amount`.
+pub fn split_by_basis_points<Balance>(amount: Balance, shares: &[u32]) -> Vec<Balance> where
+	Balance: AtLeast32BitUnsigned + Copy,
+{
+	let scale = Balance::from(MAX_BASIS_POINTS);
+	// amount * bp / scale without overflowing: (q * scale + r) * bp / scale.
+	let (quotient, remainder) = (amount / scale, amount % scale);
+	let mut parts = Vec::with_capacity(shares.len());
+	let mut remainders = Vec::with_capacity(shares.len());
+	let mut assigned = Balance::zero();
+	for (index, bp) in shares.iter().enumerate() {
+		let bp = Balance::from(*bp);
+		let part = quotient * bp + remainder * bp / scale;
+		remainders.push((remainder * bp % scale, index));
+		assigned += part;
+		parts.push(part);
+	}
+
+	remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+	let mut leftover = amount.saturating_sub(assigned);
+	for (_, index) in remainders {
+		if leftover.is_zero() {
+			break;
 		}
+		parts[index] += One::one();
+		leftover -= One::one();
 	}
+	parts
 }
@@ -0,0 +1,170 @@
+use crate as pallet_royalty;
+use sp_core::H256;
+use frame_support::{parameter_types, traits::Get, weights::{RuntimeDbWeight, Weight}};
+use sp_runtime::{
+	ModuleId, traits::{BlakeTwo256, IdentityLookup}, testing::Header,
+};
+use frame_system as system;
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Timestamp: pallet_timestamp::{Module, Call, Storage, Inherent},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		Assets: pallet_assets::{Module, Call, Storage, Event<T>},
+		RightsMgmtPortal: pallet_rmp::{Module, Call, Storage, Event<T>},
+		Royalty: pallet_royalty::{Module, Call, Storage, Config, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+	pub const MinimumPeriod: u64 = 1;
+	pub const TakedownBond: u64 = 100;
+	pub const TakedownPeriod: u64 = 10;
+	pub const AssetDepositBase: u64 = 1;
+	pub const AssetDepositPerZombie: u64 = 1;
+	pub const StringLimit: u32 = 50;
+	pub const MetadataDepositBase: u64 = 1;
+	pub const MetadataDepositPerByte: u64 = 1;
+	pub const RoyaltyModuleId: ModuleId = ModuleId(*b"py/royal");
+	pub const EscrowPeriod: u64 = 20;
+	pub const StatementPeriod: u64 = 10;
+	pub const StatementRetention: u64 = 2;
+	pub const MockDbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 1 };
+	pub const PayoutInterval: u64 = 5;
+	// Two payout steps per block.
+	pub const MaxPayoutWeight: Weight = 300;
+	pub const ProposalPeriod: u64 = 10;
+}
+
+thread_local! {
+	static EXISTENTIAL_DEPOSIT: RefCell<u64> = RefCell::new(1);
+	static APPROVAL_THRESHOLD: RefCell<u32> = RefCell::new(10_000);
+}
+
+pub struct ExistentialDeposit;
+impl Get<u64> for ExistentialDeposit {
+	fn get() -> u64 {
+		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow())
+	}
+}
+
+/// Set the existential deposit of the test, before building its externalities.
+pub fn set_existential_deposit(amount: u64) {
+	EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = amount);
+}
+
+pub struct ApprovalThreshold;
+impl Get<u32> for ApprovalThreshold {
+	fn get() -> u32 {
+		APPROVAL_THRESHOLD.with(|v| *v.borrow())
+	}
+}
+
+/// Set the share of affected payees, in basis points, whose approval enacts a split change.
+pub fn set_approval_threshold(bp: u32) {
+	APPROVAL_THRESHOLD.with(|v| *v.borrow_mut() = bp);
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = MockDbWeight;
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = u64;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = system::EnsureRoot<u64>;
+	type AssetDepositBase = AssetDepositBase;
+	type AssetDepositPerZombie = AssetDepositPerZombie;
+	type StringLimit = StringLimit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type WeightInfo = ();
+}
+
+impl pallet_rmp::Config for Test {
+	type Event = Event;
+	type GenreOrigin = system::EnsureRoot<u64>;
+	type Currency = Balances;
+	type TakedownOrigin = system::EnsureRoot<u64>;
+	type TakedownBond = TakedownBond;
+	type TakedownPeriod = TakedownPeriod;
+	type OnRightsChange = Royalty;
+	type OnTakedownSettled = Royalty;
+}
+
+impl pallet_royalty::Config for Test {
+	type Event = Event;
+	type ModuleId = RoyaltyModuleId;
+	type AssetOrigin = system::EnsureRoot<u64>;
+	type EscrowOrigin = system::EnsureRoot<u64>;
+	type EscrowPeriod = EscrowPeriod;
+	type StatementPeriod = StatementPeriod;
+	type StatementRetention = StatementRetention;
+	type WithholdingOrigin = system::EnsureRoot<u64>;
+	type PayoutInterval = PayoutInterval;
+	type MaxPayoutWeight = MaxPayoutWeight;
+	type ProposalPeriod = ProposalPeriod;
+	type ApprovalThreshold = ApprovalThreshold;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: (1..=9).map(|who| (who, 1_000)).collect(),
+	}.assimilate_storage(&mut t).unwrap();
+	pallet_royalty::GenesisConfig::default().assimilate_storage::<Test>(&mut t).unwrap();
+	t.into()
+}
@@ -0,0 +1,879 @@
+use crate::{AssetRate, Distribution, Earning, Error, EscrowBeneficiary, Payee, PayoutMode, RecoupmentScope, split_by_basis_points, mock::*};
+use frame_support::{assert_ok, assert_noop, traits::{IntegrityTest, OnInitialize, UnfilteredDispatchable}};
+use sp_runtime::{DispatchError, traits::{BlakeTwo256, Hash}};
+
+/// Binary CID of an audio file whose content is `label`.
+fn src(label: &[u8]) -> Vec<u8> {
+	pallet_rmp::cid::cid_v1(pallet_rmp::cid::CODEC_RAW, pallet_rmp::cid::SHA2_256, &sp_io::hashing::sha2_256(label))
+}
+
+fn register(owner: u64, src_id: &[u8]) {
+	assert_ok!(RightsMgmtPortal::register_music(
+		Origin::signed(owner),
+		src_id.to_vec(),
+		b"USRC17607839".to_vec(),
+		owner,
+		None,
+	));
+}
+
+fn assets(call: pallet_assets::Call<Test>, origin: Origin) {
+	assert_ok!(call.dispatch_bypass_filter(origin));
+}
+
+/// Gross royalties of each SRC in a statement.
+fn gross(earnings: &[(Vec<u8>, Earning<u64>)]) -> Vec<(Vec<u8>, u64)> {
+	earnings.iter().map(|(src_id, earning)| (src_id.clone(), *earning.gross())).collect()
+}
+
+fn distribute(owner: u64, src_id: &[u8], shares: &[(u64, u32)]) {
+	assert_ok!(Royalty::set_distributions(
+		Origin::signed(owner),
+		src_id.to_vec(),
+		shares.iter().map(|(payee, bp)| Distribution::new(*payee, *bp)).collect(),
+	));
+}
+
+#[test]
+fn splits_round_deterministically() {
+	assert_eq!(split_by_basis_points(100u64, &[3333, 3333, 3334]), vec![33, 33, 34]);
+	assert_eq!(split_by_basis_points(7u64, &[3333, 3333, 3334]), vec![2, 2, 3]);
+	assert_eq!(split_by_basis_points(2u64, &[5000, 5000]), vec![1, 1]);
+	assert_eq!(split_by_basis_points(1u64, &[5000, 5000]), vec![1, 0]);
+	assert_eq!(split_by_basis_points(u64::MAX, &[10_000]), vec![u64::MAX]);
+}
+
+#[test]
+fn owner_sets_valid_distributions() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		let set = |who: u64, shares: Vec<(u64, u32)>| Royalty::set_distributions(
+			Origin::signed(who),
+			src(b"src-1"),
+			shares.into_iter().map(|(payee, bp)| Distribution::new(payee, bp)).collect(),
+		);
+		assert_noop!(set(2, vec![(2, 10_000)]), pallet_rmp::Error::<Test>::NotSrcOwner);
+		assert_noop!(set(1, vec![]), Error::<Test>::InvalidPayeeCount);
+		assert_noop!(set(1, vec![(2, 6_000), (3, 3_000)]), Error::<Test>::InvalidShares);
+		assert_noop!(set(1, vec![(2, 10_000), (3, 0)]), Error::<Test>::InvalidShares);
+		assert_noop!(set(1, vec![(2, 5_000), (2, 5_000)]), Error::<Test>::DuplicatePayee);
+		assert_ok!(set(1, vec![(2, 7_000), (3, 3_000)]));
+		assert_eq!(Royalty::distributions_of(src(b"src-1")).len(), 2);
+	});
+}
+
+#[test]
+fn pays_royalties_to_payees_and_cleared_samples() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		register(5, &src(b"src-2"));
+		assert_noop!(
+			Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100),
+			Error::<Test>::NoDistributions
+		);
+		distribute(1, &src(b"src-1"), &[(2, 7_000), (3, 3_000)]);
+		assert_noop!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 0), Error::<Test>::ZeroAmount);
+		assert_noop!(
+			Royalty::pay_royalty(Origin::signed(4), src(b"src-3"), 100),
+			Error::<Test>::SrcNotFound
+		);
+
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_eq!(Balances::free_balance(2), 1_070);
+		assert_eq!(Balances::free_balance(3), 1_030);
+		assert_eq!(Balances::free_balance(4), 900);
+
+		// A cleared sample takes its share before the split.
+		assert_ok!(RightsMgmtPortal::declare_sample(Origin::signed(1), src(b"src-1"), src(b"src-2"), 1_000));
+		assert_ok!(RightsMgmtPortal::respond_to_sample(Origin::signed(5), src(b"src-1"), src(b"src-2"), true));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_eq!(Balances::free_balance(5), 1_010);
+		assert_eq!(Balances::free_balance(2), 1_133);
+		assert_eq!(Balances::free_balance(3), 1_057);
+	});
+}
+
+#[test]
+fn royalties_of_suspended_srcs_are_escrowed() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		distribute(1, &src(b"src-1"), &[(2, 7_000), (6, 3_000)]);
+		assert_ok!(RightsMgmtPortal::file_takedown(Origin::signed(3), src(b"src-1"), BlakeTwo256::hash(b"evidence")));
+
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(5), src(b"src-1"), 20));
+		assert_eq!(Balances::free_balance(2), 1_000);
+		assert_eq!(*Royalty::escrow_of(src(b"src-1")).unwrap().total(), 120);
+		assert_noop!(Royalty::refund_escrow(Origin::signed(4), src(b"src-1")), Error::<Test>::EscrowNotExpired);
+		assert_noop!(Royalty::release_escrow(Origin::signed(3), src(b"src-1")), DispatchError::BadOrigin);
+		assert_noop!(Royalty::release_escrow(Origin::root(), src(b"src-1")), Error::<Test>::TakedownPending);
+
+		// Once the notice is dismissed, the escrow is split as a payment made now would be.
+		assert_ok!(RightsMgmtPortal::resolve_takedown(Origin::root(), src(b"src-1"), false));
+		assert_eq!(Royalty::escrow_beneficiary_of(src(b"src-1")), Some(EscrowBeneficiary::Payees));
+		assert_ok!(Royalty::release_escrow(Origin::root(), src(b"src-1")));
+		assert_eq!(Balances::free_balance(2), 1_084);
+		assert_eq!(Balances::free_balance(6), 1_036);
+		assert_eq!(*Royalty::ledger_of(src(b"src-1")).paid(), 120);
+		assert!(Royalty::escrow_of(src(b"src-1")).is_none());
+	});
+}
+
+#[test]
+fn upheld_takedowns_release_escrows_to_the_claimant() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		distribute(1, &src(b"src-1"), &[(2, 10_000)]);
+		assets(pallet_assets::Call::force_create(0, 1, 10, 5), Origin::root());
+		assets(pallet_assets::Call::mint(0, 4, 1_000), Origin::signed(1));
+		assets(pallet_assets::Call::mint(0, Royalty::account_id(), 5), Origin::signed(1));
+		assert_ok!(Royalty::register_asset(Origin::root(), 0, AssetRate::new(1, 1)));
+		assert_ok!(RightsMgmtPortal::file_takedown(Origin::signed(3), src(b"src-1"), BlakeTwo256::hash(b"evidence")));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_ok!(Royalty::pay_royalty_in_asset(Origin::signed(4), src(b"src-1"), 0, 40));
+
+		// The SRC stays suspended, and its escrows go to the claimant rather than the payees.
+		assert_ok!(RightsMgmtPortal::resolve_takedown(Origin::root(), src(b"src-1"), true));
+		assert_eq!(Royalty::escrow_beneficiary_of(src(b"src-1")), Some(EscrowBeneficiary::Claimant(3)));
+		assert_ok!(Royalty::release_escrow(Origin::root(), src(b"src-1")));
+		assert_ok!(Royalty::release_asset_escrow(Origin::root(), src(b"src-1"), 0));
+		assert_eq!(Balances::free_balance(3), 1_100);
+		assert_eq!(Assets::balance(0, 3), 40);
+		assert_eq!(Balances::free_balance(2), 1_000);
+		assert_eq!(Assets::balance(0, 2), 0);
+		assert!(Royalty::escrow_of(src(b"src-1")).is_none());
+		assert!(Royalty::asset_escrow_of(src(b"src-1"), 0).is_none());
+	});
+}
+
+#[test]
+fn expired_escrows_are_refunded() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		assert_ok!(RightsMgmtPortal::file_takedown(Origin::signed(3), src(b"src-1"), BlakeTwo256::hash(b"evidence")));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(5), src(b"src-1"), 50));
+
+		// A payment after expiry refunds the escrow and opens a new one.
+		System::set_block_number(20);
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 10));
+		assert_eq!(Balances::free_balance(4), 990);
+		assert_eq!(Balances::free_balance(5), 1_000);
+		let escrow = Royalty::escrow_of(src(b"src-1")).unwrap();
+		assert_eq!((*escrow.total(), *escrow.expires()), (10, 40));
+		assert_noop!(Royalty::refund_escrow(Origin::signed(6), src(b"src-1")), Error::<Test>::EscrowNotExpired);
+
+		System::set_block_number(40);
+		assert_ok!(Royalty::refund_escrow(Origin::signed(6), src(b"src-1")));
+		assert_eq!(Balances::free_balance(4), 1_000);
+		assert_noop!(Royalty::refund_escrow(Origin::signed(6), src(b"src-1")), Error::<Test>::EscrowNotFound);
+	});
+}
+
+#[test]
+fn failed_escrow_refunds_stay_in_escrow() {
+	set_existential_deposit(500);
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		assert_ok!(RightsMgmtPortal::file_takedown(Origin::signed(3), src(b"src-1"), BlakeTwo256::hash(b"evidence")));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(5), src(b"src-1"), 100));
+		assert_ok!(Balances::transfer(Origin::signed(4), 7, 900));
+
+		// The refund of 4, reaped meanwhile, is below the existential deposit; 5 is still refunded.
+		System::set_block_number(20);
+		assert_ok!(Royalty::refund_escrow(Origin::signed(6), src(b"src-1")));
+		assert_eq!(Balances::free_balance(5), 1_000);
+		let escrow = Royalty::escrow_of(src(b"src-1")).unwrap();
+		assert_eq!((*escrow.total(), escrow.deposits().to_vec()), (100, vec![(4, 100)]));
+
+		assert_ok!(Balances::transfer(Origin::signed(7), 4, 900));
+		assert_ok!(Royalty::refund_escrow(Origin::signed(6), src(b"src-1")));
+		assert_eq!(Balances::free_balance(4), 1_000);
+		assert!(Royalty::escrow_of(src(b"src-1")).is_none());
+	});
+}
+
+#[test]
+fn pushed_payouts_below_existential_deposit_accrue() {
+	set_existential_deposit(500);
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		distribute(1, &src(b"src-1"), &[(2, 5_000), (10, 5_000)]);
+
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 400));
+		assert_eq!(Balances::free_balance(2), 1_200);
+		assert_eq!(Balances::free_balance(10), 0);
+		assert_eq!(Royalty::accrued(10, src(b"src-1")), 200);
+		assert_ok!(Royalty::pay_royalty(Origin::signed(5), src(b"src-1"), 400));
+		assert_eq!(Royalty::accrued(10, src(b"src-1")), 400);
+		assert_noop!(Royalty::claim(Origin::signed(10), src(b"src-1")), pallet_balances::Error::<Test>::ExistentialDeposit);
+
+		assert_ok!(Royalty::pay_royalty(Origin::signed(6), src(b"src-1"), 400));
+		assert_ok!(Royalty::claim(Origin::signed(10), src(b"src-1")));
+		assert_eq!(Balances::free_balance(10), 600);
+		assert_eq!(Balances::free_balance(2), 1_600);
+	});
+}
+
+#[test]
+fn accrued_royalties_are_claimed_by_payees() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		distribute(1, &src(b"src-1"), &[(2, 7_000), (3, 3_000)]);
+		assert_noop!(
+			Royalty::set_payout_mode(Origin::signed(2), src(b"src-1"), PayoutMode::Accrue),
+			pallet_rmp::Error::<Test>::NotSrcOwner
+		);
+		assert_ok!(Royalty::set_payout_mode(Origin::signed(1), src(b"src-1"), PayoutMode::Accrue));
+
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(5), src(b"src-1"), 10));
+		assert_eq!(Balances::free_balance(2), 1_000);
+		assert_eq!(Balances::free_balance(Royalty::account_id()), 111);
+		assert_eq!(Royalty::accrued_of(&2), vec![(src(b"src-1"), 77)]);
+
+		assert_ok!(Royalty::claim(Origin::signed(2), src(b"src-1")));
+		assert_eq!(Balances::free_balance(2), 1_077);
+		assert_noop!(Royalty::claim(Origin::signed(2), src(b"src-1")), Error::<Test>::NothingToClaim);
+		let ledger = Royalty::ledger_of(src(b"src-1"));
+		assert_eq!((*ledger.paid(), *ledger.claimed(), *ledger.outstanding()), (110, 77, 33));
+
+		// Pushed payments are accounted for as claimed at once.
+		assert_ok!(Royalty::set_payout_mode(Origin::signed(1), src(b"src-1"), PayoutMode::Push));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_ok!(Royalty::claim(Origin::signed(3), src(b"src-1")));
+		assert_eq!(Balances::free_balance(3), 1_063);
+		assert_eq!(Balances::free_balance(Royalty::account_id()), 1);
+		let ledger = Royalty::ledger_of(src(b"src-1"));
+		assert_eq!((*ledger.paid(), *ledger.claimed(), *ledger.outstanding()), (210, 210, 0));
+	});
+}
+
+#[test]
+fn pays_royalties_in_assets() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		distribute(1, &src(b"src-1"), &[(2, 9_000), (3, 1_000)]);
+		assets(pallet_assets::Call::force_create(0, 1, 10, 5), Origin::root());
+		assets(pallet_assets::Call::mint(0, 4, 1_000), Origin::signed(1));
+
+		// One unit of the asset is worth two of the native currency.
+		let rate = AssetRate::new(2, 1);
+		assert_noop!(Royalty::register_asset(Origin::signed(1), 0, rate), DispatchError::BadOrigin);
+		assert_noop!(Royalty::register_asset(Origin::root(), 0, rate), Error::<Test>::AssetReserveMissing);
+		assets(pallet_assets::Call::mint(0, Royalty::account_id(), 5), Origin::signed(1));
+		assert_noop!(
+			Royalty::register_asset(Origin::root(), 0, AssetRate::new(0, 1)),
+			Error::<Test>::InvalidAssetRate
+		);
+		assert_ok!(Royalty::register_asset(Origin::root(), 0, rate));
+		assert_noop!(
+			Royalty::pay_royalty_in_asset(Origin::signed(4), src(b"src-1"), 1, 40),
+			Error::<Test>::AssetNotAccepted
+		);
+
+		// The share of 3 is below the asset's minimum balance and accrues until claimable.
+		assert_ok!(Royalty::pay_royalty_in_asset(Origin::signed(4), src(b"src-1"), 0, 40));
+		assert_eq!(Assets::balance(0, 2), 36);
+		assert_eq!(Assets::balance(0, 3), 0);
+		assert_eq!(Royalty::accrued_assets_of(&3), vec![(src(b"src-1"), 0, 4)]);
+		assert!(Royalty::claim_asset(Origin::signed(3), src(b"src-1"), 0).is_err());
+		assert_eq!(Royalty::accrued_asset(3, (src(b"src-1"), 0)), 4);
+
+		assert_ok!(Royalty::pay_royalty_in_asset(Origin::signed(4), src(b"src-1"), 0, 20));
+		assert_ok!(Royalty::claim_asset(Origin::signed(3), src(b"src-1"), 0));
+		assert_eq!(Assets::balance(0, 2), 54);
+		assert_eq!(Assets::balance(0, 3), 6);
+		assert_eq!(Assets::balance(0, Royalty::account_id()), 5);
+		let ledger = Royalty::asset_ledger_of(src(b"src-1"), 0);
+		assert_eq!((*ledger.paid(), *ledger.claimed(), *ledger.outstanding()), (60, 60, 0));
+		// Native royalties are accounted for separately.
+		assert_eq!(*Royalty::ledger_of(src(b"src-1")).paid(), 0);
+
+		// Advances are recouped from payments in the asset at its rate.
+		assert_ok!(Royalty::create_recoupment(Origin::signed(1), RecoupmentScope::Src(src(b"src-1")), 6, 100, 5_000));
+		assert_ok!(Royalty::pay_royalty_in_asset(Origin::signed(4), src(b"src-1"), 0, 40));
+		assert_eq!(Assets::balance(0, 6), 20);
+		assert_eq!(Assets::balance(0, 2), 72);
+		assert_eq!(Royalty::recoupment(0).unwrap().unrecouped(), 60);
+		// The last part only takes what the unrecouped balance is worth.
+		assert_ok!(Royalty::pay_royalty_in_asset(Origin::signed(4), src(b"src-1"), 0, 200));
+		assert_eq!(Assets::balance(0, 6), 50);
+		assert_eq!(Assets::balance(0, 2), 225);
+		assert_eq!(Assets::balance(0, 3), 25);
+		assert_eq!(Royalty::recoupment(0).unwrap().unrecouped(), 0);
+		assert!(Royalty::recoupments_of(src(b"src-1")).is_empty());
+	});
+}
+
+#[test]
+fn asset_royalties_of_suspended_srcs_are_escrowed() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		distribute(1, &src(b"src-1"), &[(2, 10_000)]);
+		assets(pallet_assets::Call::force_create(0, 1, 10, 5), Origin::root());
+		assets(pallet_assets::Call::mint(0, 4, 1_000), Origin::signed(1));
+		assets(pallet_assets::Call::mint(0, Royalty::account_id(), 5), Origin::signed(1));
+		assert_ok!(Royalty::register_asset(Origin::root(), 0, AssetRate::new(1, 1)));
+		assert_ok!(RightsMgmtPortal::file_takedown(Origin::signed(3), src(b"src-1"), BlakeTwo256::hash(b"evidence")));
+
+		assert_ok!(Royalty::pay_royalty_in_asset(Origin::signed(4), src(b"src-1"), 0, 40));
+		assert_eq!(Assets::balance(0, 2), 0);
+		assert_eq!(*Royalty::asset_escrow_of(src(b"src-1"), 0).unwrap().total(), 40);
+		assert_noop!(
+			Royalty::release_asset_escrow(Origin::root(), src(b"src-1"), 0),
+			Error::<Test>::TakedownPending
+		);
+
+		assert_ok!(RightsMgmtPortal::resolve_takedown(Origin::root(), src(b"src-1"), false));
+		assert_ok!(Royalty::release_asset_escrow(Origin::root(), src(b"src-1"), 0));
+		assert_eq!(Assets::balance(0, 2), 40);
+		assert!(Royalty::asset_escrow_of(src(b"src-1"), 0).is_none());
+	});
+}
+
+#[test]
+fn advances_are_recouped_after_samples_before_splits() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		register(5, &src(b"src-2"));
+		distribute(1, &src(b"src-1"), &[(2, 10_000)]);
+		assert_ok!(RightsMgmtPortal::declare_sample(Origin::signed(1), src(b"src-1"), src(b"src-2"), 2_000));
+		assert_ok!(RightsMgmtPortal::respond_to_sample(Origin::signed(5), src(b"src-1"), src(b"src-2"), true));
+		let scope = RecoupmentScope::Src(src(b"src-1"));
+		assert_noop!(
+			Royalty::create_recoupment(Origin::signed(2), scope.clone(), 6, 100, 5_000),
+			pallet_rmp::Error::<Test>::NotSrcOwner
+		);
+		assert_noop!(
+			Royalty::create_recoupment(Origin::signed(1), scope.clone(), 6, 100, 10_001),
+			Error::<Test>::InvalidRecoupment
+		);
+		assert_ok!(Royalty::create_recoupment(Origin::signed(1), scope, 6, 100, 5_000));
+
+		// The cleared sample is paid first and the advance recouped from the rest.
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 125));
+		assert_eq!(Balances::free_balance(5), 1_025);
+		assert_eq!(Balances::free_balance(6), 1_050);
+		assert_eq!(Balances::free_balance(2), 1_050);
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 125));
+		assert_eq!(Balances::free_balance(6), 1_100);
+		assert_eq!(Balances::free_balance(2), 1_100);
+		assert_eq!(Royalty::recoupment(0).unwrap().unrecouped(), 0);
+		assert!(Royalty::recoupments_of(src(b"src-1")).is_empty());
+
+		// Once recouped, royalties are split as usual.
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_eq!(Balances::free_balance(2), 1_180);
+		assert_eq!(Balances::free_balance(5), 1_070);
+		assert_eq!(Balances::free_balance(6), 1_100);
+	});
+}
+
+#[test]
+fn split_change_proposals_end_when_rights_change_hands() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		register(1, &src(b"src-1"));
+		distribute(1, &src(b"src-1"), &[(1, 5_000), (5, 5_000)]);
+		assert_ok!(Royalty::propose_split_change(Origin::signed(1), src(b"src-1"), vec![
+			Distribution::new(Payee::Account(1), 5_000),
+			Distribution::new(Payee::Account(3), 5_000),
+		]));
+		// The owner's proposal goes with the rights.
+		assert_ok!(RightsMgmtPortal::grant_rights(Origin::signed(1), src(b"src-1"), 2, 10));
+		assert_eq!(Royalty::proposal_of(src(b"src-1")), None);
+		assert_eq!(Royalty::split_proposal(0), None);
+
+		// So does the grantee's once they revert.
+		assert_ok!(Royalty::propose_split_change(Origin::signed(2), src(b"src-1"), vec![
+			Distribution::new(Payee::Account(1), 5_000),
+			Distribution::new(Payee::Account(4), 5_000),
+		]));
+		assert_eq!(Royalty::proposal_of(src(b"src-1")), Some(1));
+		RightsMgmtPortal::on_initialize(11);
+		assert_eq!(RightsMgmtPortal::owner_of(src(b"src-1")), Some(1));
+		assert_eq!(Royalty::proposal_of(src(b"src-1")), None);
+		assert_eq!(Royalty::split_proposal(1), None);
+		assert_eq!(Royalty::distributions_of(src(b"src-1")), vec![
+			Distribution::new(Payee::Account(1), 5_000),
+			Distribution::new(Payee::Account(5), 5_000),
+		]);
+	});
+}
+
+#[test]
+fn album_advances_are_recouped_across_tracks() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(RightsMgmtPortal::register_embargoed_album(
+			Origin::signed(1),
+			b"album-1".to_vec(),
+			1,
+			b"036000291452".to_vec(),
+			BlakeTwo256::hash(b"metadata"),
+			100,
+		));
+		register(1, &src(b"src-2"));
+		register(1, &src(b"src-3"));
+		distribute(1, &src(b"src-2"), &[(2, 10_000)]);
+		distribute(1, &src(b"src-3"), &[(3, 10_000)]);
+		let scope = RecoupmentScope::Album(b"album-1".to_vec(), vec![src(b"src-2"), src(b"src-3")]);
+		assert_noop!(
+			Royalty::create_recoupment(Origin::signed(2), scope.clone(), 6, 50, 10_000),
+			Error::<Test>::NotAlbumOwner
+		);
+		assert_ok!(RightsMgmtPortal::set_album_tracks(Origin::signed(1), b"album-1".to_vec(), vec![src(b"src-2")]));
+		assert_noop!(
+			Royalty::create_recoupment(Origin::signed(1), scope.clone(), 6, 50, 10_000),
+			Error::<Test>::TrackNotOnAlbum
+		);
+		assert_ok!(RightsMgmtPortal::set_album_tracks(
+			Origin::signed(1),
+			b"album-1".to_vec(),
+			vec![src(b"src-2"), src(b"src-3")],
+		));
+		assert_ok!(Royalty::create_recoupment(Origin::signed(1), scope, 6, 50, 10_000));
+
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-2"), 30));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-3"), 30));
+		assert_eq!(Balances::free_balance(6), 1_050);
+		assert_eq!(Balances::free_balance(2), 1_000);
+		assert_eq!(Balances::free_balance(3), 1_010);
+		assert!(Royalty::recoupments_of(src(b"src-2")).is_empty());
+	});
+}
+
+#[test]
+fn nested_splits_cascade_through_groups_and_srcs() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		register(5, &src(b"src-2"));
+		assert_ok!(Royalty::create_split_group(
+			Origin::signed(6),
+			vec![Distribution::new(Payee::Account(2), 5_000), Distribution::new(Payee::Account(3), 5_000)],
+		));
+		assert_ok!(Royalty::set_distributions(Origin::signed(1), src(b"src-1"), vec![
+			Distribution::new(Payee::Group(0), 6_000),
+			Distribution::new(Payee::Src(src(b"src-2")), 2_000),
+			Distribution::new(Payee::Account(7), 2_000),
+		]));
+
+		// The group splits its share further and src-2, without a table, pays its owner.
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_eq!(Balances::free_balance(2), 1_030);
+		assert_eq!(Balances::free_balance(3), 1_030);
+		assert_eq!(Balances::free_balance(5), 1_020);
+		assert_eq!(Balances::free_balance(7), 1_020);
+
+		// src-2 pays its cleared sample as if paid directly, and its escrow while suspended.
+		register(8, &src(b"src-3"));
+		assert_ok!(RightsMgmtPortal::declare_sample(Origin::signed(5), src(b"src-2"), src(b"src-3"), 5_000));
+		assert_ok!(RightsMgmtPortal::respond_to_sample(Origin::signed(8), src(b"src-2"), src(b"src-3"), true));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_eq!(Balances::free_balance(5), 1_030);
+		assert_eq!(Balances::free_balance(8), 10);
+		assert_ok!(RightsMgmtPortal::file_takedown(Origin::signed(9), src(b"src-2"), BlakeTwo256::hash(b"evidence")));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_eq!(Balances::free_balance(2), 1_090);
+		assert_eq!(Balances::free_balance(5), 1_030);
+		assert_eq!(Balances::free_balance(7), 1_060);
+		assert_eq!(*Royalty::escrow_of(src(b"src-2")).unwrap().total(), 20);
+		assert_eq!(*Royalty::ledger_of(src(b"src-1")).paid(), 280);
+
+		assert_noop!(
+			Royalty::update_split_group(Origin::signed(2), 0, vec![Distribution::new(Payee::Account(2), 10_000)]),
+			Error::<Test>::NotSplitGroupOwner
+		);
+		assert_noop!(
+			Royalty::update_split_group(Origin::signed(6), 0, vec![Distribution::new(Payee::Src(src(b"src-1")), 10_000)]),
+			Error::<Test>::SplitCycle
+		);
+		assert_noop!(
+			Royalty::create_split_group(Origin::signed(6), vec![Distribution::new(Payee::Group(9), 10_000)]),
+			Error::<Test>::SplitGroupNotFound
+		);
+	});
+}
+
+#[test]
+fn nested_splits_are_depth_limited() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Royalty::create_split_group(Origin::signed(1), vec![Distribution::new(Payee::Account(2), 10_000)]));
+		for parent in 0..3 {
+			assert_ok!(Royalty::create_split_group(Origin::signed(1), vec![Distribution::new(Payee::Group(parent), 10_000)]));
+		}
+		assert_noop!(
+			Royalty::create_split_group(Origin::signed(1), vec![Distribution::new(Payee::Group(3), 10_000)]),
+			Error::<Test>::SplitTooDeep
+		);
+
+		// A table deepened after its parents were set pays the owner of the nested table
+		// past the limit.
+		register(1, &src(b"src-1"));
+		assert_ok!(Royalty::set_distributions(Origin::signed(1), src(b"src-1"), vec![
+			Distribution::new(Payee::Group(2), 10_000),
+		]));
+		assert_ok!(Royalty::create_split_group(Origin::signed(8), vec![Distribution::new(Payee::Account(3), 10_000)]));
+		assert_ok!(Royalty::update_split_group(Origin::signed(1), 0, vec![Distribution::new(Payee::Group(4), 10_000)]));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_eq!(Balances::free_balance(8), 100);
+		assert_eq!(Balances::free_balance(3), 1_000);
+	});
+}
+
+#[test]
+fn statements_snapshot_earnings_per_period() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		register(1, &src(b"src-2"));
+		distribute(1, &src(b"src-1"), &[(2, 7_000), (3, 3_000)]);
+		distribute(1, &src(b"src-2"), &[(2, 10_000)]);
+
+		System::set_block_number(1);
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-2"), 50));
+		assert_ok!(Royalty::set_payout_mode(Origin::signed(1), src(b"src-1"), PayoutMode::Accrue));
+		System::set_block_number(12);
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 10));
+		assert_eq!(Royalty::current_period(), 1);
+
+		let statement = Royalty::statement(&2, 0);
+		let mut earnings = vec![(src(b"src-1"), 70), (src(b"src-2"), 50)];
+		earnings.sort();
+		assert_eq!(gross(statement.earnings()), earnings);
+		assert_eq!((*statement.first_block(), *statement.last_block()), (0, 9));
+		// Accrued royalties are earned when credited, not when claimed.
+		let statement = Royalty::statement(&2, 1);
+		assert_eq!(gross(statement.earnings()), vec![(src(b"src-1"), 7)]);
+		assert_eq!((*statement.first_block(), *statement.last_block()), (10, 19));
+		assert!(Royalty::statement(&3, 2).earnings().is_empty());
+	});
+}
+
+#[test]
+fn statements_are_pruned_after_retention() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		distribute(1, &src(b"src-1"), &[(2, 10_000)]);
+		System::set_block_number(1);
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		System::set_block_number(12);
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 50));
+
+		// Two periods are kept before the current one.
+		System::set_block_number(30);
+		Royalty::on_initialize(30);
+		assert!(Royalty::statement(&2, 0).earnings().is_empty());
+		assert!(!Royalty::has_statement(0, 2));
+		assert_eq!(gross(Royalty::statement(&2, 1).earnings()), vec![(src(b"src-1"), 50)]);
+		assert_eq!(Royalty::first_retained_period(), 1);
+
+		System::set_block_number(40);
+		Royalty::on_initialize(40);
+		assert!(Royalty::statement(&2, 1).earnings().is_empty());
+		assert_eq!(Royalty::first_retained_period(), 2);
+	});
+}
+
+#[test]
+fn withholding_goes_to_tax_account() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		distribute(1, &src(b"src-1"), &[(2, 7_000), (3, 3_000)]);
+		assert_noop!(
+			Royalty::set_withholding_rate(Origin::root(), 2, 3_000),
+			Error::<Test>::NoTaxAccount
+		);
+		assert_ok!(Royalty::set_tax_account(Origin::root(), 9));
+		assert_noop!(
+			Royalty::set_withholding_rate(Origin::root(), 2, 10_001),
+			Error::<Test>::InvalidWithholdingRate
+		);
+		assert_noop!(
+			Royalty::set_withholding_rate(Origin::signed(2), 2, 0),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Royalty::set_withholding_rate(Origin::root(), 2, 3_000));
+
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_eq!(Balances::free_balance(2), 1_049);
+		assert_eq!(Balances::free_balance(3), 1_030);
+		assert_eq!(Balances::free_balance(9), 1_021);
+
+		let statement = Royalty::statement(&2, 0);
+		let earning = statement.earnings()[0].1;
+		assert_eq!((*earning.gross(), *earning.withheld(), earning.net()), (70, 21, 49));
+		let earning = Royalty::statement(&3, 0).earnings()[0].1;
+		assert_eq!((*earning.gross(), *earning.withheld(), earning.net()), (30, 0, 30));
+		// Withheld royalties are not earnings of the tax account.
+		assert!(Royalty::statement(&9, 0).earnings().is_empty());
+
+		assert_ok!(Royalty::set_withholding_rate(Origin::root(), 2, 0));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_eq!(Balances::free_balance(2), 1_119);
+		assert_eq!(Balances::free_balance(9), 1_021);
+	});
+}
+
+#[test]
+fn withholding_spares_sample_owners_and_advancers() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		register(5, &src(b"src-2"));
+		distribute(1, &src(b"src-1"), &[(2, 10_000)]);
+		assert_ok!(RightsMgmtPortal::declare_sample(Origin::signed(1), src(b"src-1"), src(b"src-2"), 2_000));
+		assert_ok!(RightsMgmtPortal::respond_to_sample(Origin::signed(5), src(b"src-1"), src(b"src-2"), true));
+		assert_ok!(Royalty::create_recoupment(Origin::signed(1), RecoupmentScope::Src(src(b"src-1")), 6, 100, 5_000));
+		assert_ok!(Royalty::set_tax_account(Origin::root(), 9));
+		for payee in &[2, 5, 6] {
+			assert_ok!(Royalty::set_withholding_rate(Origin::root(), *payee, 3_000));
+		}
+
+		// Only the payee's share of the table is withheld from.
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		assert_eq!(Balances::free_balance(5), 1_020);
+		assert_eq!(Balances::free_balance(6), 1_040);
+		assert_eq!(Balances::free_balance(2), 1_028);
+		assert_eq!(Balances::free_balance(9), 1_012);
+		let earning = Royalty::statement(&5, 0).earnings()[0].1;
+		assert_eq!((*earning.gross(), *earning.withheld(), earning.net()), (20, 0, 20));
+		let earning = Royalty::statement(&6, 0).earnings()[0].1;
+		assert_eq!((*earning.gross(), *earning.withheld(), earning.net()), (40, 0, 40));
+	});
+}
+
+#[test]
+fn payouts_below_threshold_accrue_until_payout_run() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		distribute(1, &src(b"src-1"), &[(2, 7_000), (3, 3_000)]);
+		assert_ok!(Royalty::set_payout_threshold(Origin::signed(2), 50));
+
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 50));
+		assert_eq!(Balances::free_balance(2), 1_000);
+		assert_eq!(Balances::free_balance(3), 1_015);
+		assert_eq!(Royalty::accrued(2, src(b"src-1")), 35);
+		// Not enough accrued yet: the payee moves to the next run.
+		Royalty::on_initialize(5);
+		assert_eq!(Balances::free_balance(2), 1_000);
+		assert_eq!(Royalty::payout_run_end(), None);
+
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 50));
+		Royalty::on_initialize(6);
+		assert_eq!(Balances::free_balance(2), 1_000);
+		Royalty::on_initialize(10);
+		assert_eq!(Balances::free_balance(2), 1_070);
+		assert_eq!(Royalty::accrued(2, src(b"src-1")), 0);
+		assert_eq!(Royalty::accrued_total_of(2), 0);
+		assert!(!Royalty::in_payout_queue(2));
+		let ledger = Royalty::ledger_of(src(b"src-1"));
+		assert_eq!((*ledger.paid(), *ledger.claimed(), *ledger.outstanding()), (100, 100, 0));
+	});
+}
+
+#[test]
+fn payout_runs_are_weight_bounded_and_resume() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		let payees = [2, 3, 5, 6, 7];
+		distribute(1, &src(b"src-1"), &payees.iter().map(|payee| (*payee, 2_000)).collect::<Vec<_>>());
+		assert_ok!(Royalty::set_payout_mode(Origin::signed(1), src(b"src-1"), PayoutMode::Accrue));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 100));
+		// Accrued before opting in, queued by setting the threshold.
+		for payee in &payees {
+			assert_ok!(Royalty::set_payout_threshold(Origin::signed(*payee), 10));
+		}
+
+		let settled = || payees.iter().filter(|payee| Balances::free_balance(**payee) == 1_020).count();
+		Royalty::integrity_test();
+		Royalty::on_initialize(5);
+		assert_eq!(settled(), 2);
+		assert_eq!(Royalty::payout_run_end(), Some(5));
+		Royalty::on_initialize(6);
+		assert_eq!(settled(), 4);
+		Royalty::on_initialize(7);
+		assert_eq!(settled(), 5);
+		assert_eq!(Royalty::payout_run_end(), None);
+		assert_eq!(*Royalty::ledger_of(src(b"src-1")).outstanding(), 0);
+	});
+}
+
+#[test]
+fn payout_runs_settle_srcs_together_and_skip_failing_payees() {
+	set_existential_deposit(100);
+	new_test_ext().execute_with(|| {
+		assert_ok!(Royalty::set_payout_threshold(Origin::signed(10), 10));
+		assert_ok!(Royalty::set_payout_threshold(Origin::signed(11), 10));
+		let srcs: Vec<_> = (0..17u8).map(|i| src(&[b's', i])).collect();
+		for src_id in &srcs {
+			register(1, src_id);
+			distribute(1, src_id, &[(10, 10_000)]);
+			assert_ok!(Royalty::set_payout_mode(Origin::signed(1), src_id.clone(), PayoutMode::Accrue));
+			assert_ok!(Royalty::pay_royalty(Origin::signed(4), src_id.clone(), 20));
+		}
+		register(1, &src(b"src-1"));
+		distribute(1, &src(b"src-1"), &[(11, 10_000)]);
+		assert_ok!(Royalty::set_payout_mode(Origin::signed(1), src(b"src-1"), PayoutMode::Accrue));
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 50));
+
+		// Each payout of 20 is too small to open the account of 10, but they settle together.
+		Royalty::on_initialize(5);
+		assert_eq!(Balances::free_balance(10), 340);
+		assert_eq!(Royalty::accrued_total_of(10), 0);
+		// 50 cannot open the account of 11 either: it leaves the queue and the run completes.
+		Royalty::on_initialize(6);
+		assert_eq!(Balances::free_balance(11), 0);
+		assert!(!Royalty::in_payout_queue(11));
+		assert_eq!(Royalty::accrued(11, src(b"src-1")), 50);
+		assert_eq!(Royalty::payout_run_end(), None);
+
+		assert_ok!(Royalty::pay_royalty(Origin::signed(4), src(b"src-1"), 60));
+		assert!(Royalty::in_payout_queue(11));
+		Royalty::on_initialize(10);
+		assert_eq!(Balances::free_balance(11), 110);
+		assert_eq!(*Royalty::ledger_of(src(b"src-1")).outstanding(), 0);
+	});
+}
+
+#[test]
+fn split_changes_need_the_consent_of_affected_payees() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		distribute(1, &src(b"src-1"), &[(2, 7_000), (3, 3_000)]);
+		assert_noop!(
+			Royalty::set_distributions(Origin::signed(1), src(b"src-1"), vec![Distribution::new(Payee::Account(1), 10_000)]),
+			Error::<Test>::SplitChangeRequiresProposal
+		);
+
+		System::set_block_number(2);
+		let amended = vec![
+			Distribution::new(Payee::Account(2), 6_000),
+			Distribution::new(Payee::Account(3), 3_000),
+			Distribution::new(Payee::Account(4), 1_000),
+		];
+		assert_noop!(
+			Royalty::propose_split_change(Origin::signed(2), src(b"src-1"), amended.clone()),
+			pallet_rmp::Error::<Test>::NotSrcOwner
+		);
+		assert_ok!(Royalty::propose_split_change(Origin::signed(1), src(b"src-1"), amended.clone()));
+		assert_noop!(
+			Royalty::propose_split_change(Origin::signed(1), src(b"src-1"), amended.clone()),
+			Error::<Test>::ProposalPending
+		);
+		// Payee 3 keeps their share and is not asked; 2 loses some and 4 joins.
+		assert_eq!(Royalty::split_proposal(0).unwrap().approvers(), &[2, 4][..]);
+		assert_noop!(Royalty::approve_split_change(Origin::signed(3), 0), Error::<Test>::NotApprover);
+
+		assert_ok!(Royalty::approve_split_change(Origin::signed(2), 0));
+		assert_noop!(Royalty::approve_split_change(Origin::signed(2), 0), Error::<Test>::AlreadyVoted);
+		assert_eq!(Royalty::distributions_of(src(b"src-1")).len(), 2);
+		System::set_block_number(5);
+		assert_ok!(Royalty::approve_split_change(Origin::signed(4), 0));
+		assert_eq!(Royalty::distributions_of(src(b"src-1")), amended);
+		assert_eq!(Royalty::proposal_of(src(b"src-1")), None);
+
+		let history = Royalty::split_history(src(b"src-1"));
+		assert_eq!(history.iter().map(|revision| *revision.effective_from()).collect::<Vec<_>>(), vec![0, 5]);
+		assert_eq!(history[0].distributions().len(), 2);
+		assert_eq!(history[1].distributions(), &amended[..]);
+	});
+}
+
+#[test]
+fn split_changes_can_be_defeated_cancelled_or_expire() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		distribute(1, &src(b"src-1"), &[(2, 7_000), (3, 3_000)]);
+		let amended = || vec![Distribution::new(Payee::Account(2), 5_000), Distribution::new(Payee::Account(3), 5_000)];
+
+		assert_ok!(Royalty::propose_split_change(Origin::signed(1), src(b"src-1"), amended()));
+		assert_ok!(Royalty::reject_split_change(Origin::signed(2), 0));
+		assert_eq!(Royalty::split_proposal(0), None);
+		assert_eq!(Royalty::proposal_of(src(b"src-1")), None);
+
+		assert_ok!(Royalty::propose_split_change(Origin::signed(1), src(b"src-1"), amended()));
+		assert_noop!(Royalty::cancel_split_change(Origin::signed(2), 1), Error::<Test>::NotProposer);
+		assert_ok!(Royalty::cancel_split_change(Origin::signed(1), 1));
+		assert_eq!(Royalty::proposal_of(src(b"src-1")), None);
+
+		System::set_block_number(1);
+		assert_ok!(Royalty::propose_split_change(Origin::signed(1), src(b"src-1"), amended()));
+		assert_eq!(*Royalty::split_proposal(2).unwrap().expires(), 11);
+		Royalty::on_initialize(11);
+		assert_eq!(Royalty::split_proposal(2), None);
+		assert_noop!(Royalty::approve_split_change(Origin::signed(2), 2), Error::<Test>::ProposalNotFound);
+		assert_eq!(Royalty::distributions_of(src(b"src-1")).len(), 2);
+		assert_eq!(Royalty::split_history(src(b"src-1")).len(), 1);
+	});
+}
+
+#[test]
+fn split_changes_affecting_only_the_owner_apply_at_once() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		distribute(1, &src(b"src-1"), &[(1, 7_000), (3, 3_000)]);
+		let amended = vec![Distribution::new(Payee::Account(3), 3_000), Distribution::new(Payee::Account(1), 7_000)];
+		// Reordering the table changes nobody's share.
+		assert_ok!(Royalty::propose_split_change(Origin::signed(1), src(b"src-1"), amended.clone()));
+		assert_eq!(Royalty::distributions_of(src(b"src-1")), amended);
+		// The owner approves by proposing, but the new payee is asked too.
+		let amended = vec![Distribution::new(Payee::Account(3), 3_000), Distribution::new(Payee::Account(5), 7_000)];
+		assert_ok!(Royalty::propose_split_change(Origin::signed(1), src(b"src-1"), amended.clone()));
+		assert_eq!(Royalty::split_proposal(1).unwrap().approvals(), &[1][..]);
+		assert_ok!(Royalty::approve_split_change(Origin::signed(5), 1));
+		assert_eq!(Royalty::distributions_of(src(b"src-1")), amended);
+		assert_eq!(Royalty::proposal_of(src(b"src-1")), None);
+	});
+}
+
+#[test]
+fn split_changes_are_cancelled_when_a_nested_owner_changes() {
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		register(5, &src(b"src-2"));
+		assert_ok!(Royalty::set_distributions(Origin::signed(1), src(b"src-1"), vec![
+			Distribution::new(Payee::Src(src(b"src-2")), 5_000),
+			Distribution::new(Payee::Account(2), 5_000),
+		]));
+		assert_ok!(Royalty::propose_split_change(Origin::signed(1), src(b"src-1"), vec![
+			Distribution::new(Payee::Account(2), 5_000),
+			Distribution::new(Payee::Account(3), 5_000),
+		]));
+		// The owner of src-2 answers for it.
+		assert_eq!(Royalty::split_proposal(0).unwrap().approvers(), &[5, 3][..]);
+
+		// Once src-2 changes hands, the proposal no longer asks the right owner.
+		assert_ok!(RightsMgmtPortal::grant_rights(Origin::signed(5), src(b"src-2"), 6, 10));
+		assert_ok!(Royalty::approve_split_change(Origin::signed(3), 0));
+		assert_eq!(Royalty::split_proposal(0), None);
+		assert_eq!(Royalty::proposal_of(src(b"src-1")), None);
+		assert_eq!(Royalty::distributions_of(src(b"src-1"))[0], Distribution::new(Payee::Src(src(b"src-2")), 5_000));
+	});
+}
+
+#[test]
+fn split_changes_pass_with_a_majority_threshold() {
+	set_approval_threshold(5_000);
+	new_test_ext().execute_with(|| {
+		register(1, &src(b"src-1"));
+		distribute(1, &src(b"src-1"), &[(2, 4_000), (3, 3_000), (5, 3_000)]);
+		let amended = vec![
+			Distribution::new(Payee::Account(2), 2_000),
+			Distribution::new(Payee::Account(3), 2_000),
+			Distribution::new(Payee::Account(5), 2_000),
+			Distribution::new(Payee::Account(6), 4_000),
+		];
+		assert_ok!(Royalty::propose_split_change(Origin::signed(1), src(b"src-1"), amended.clone()));
+		assert_eq!(Royalty::split_proposal(0).unwrap().approvers(), &[2, 3, 5, 6][..]);
+
+		assert_ok!(Royalty::approve_split_change(Origin::signed(2), 0));
+		assert_noop!(Royalty::approve_split_change(Origin::signed(2), 0), Error::<Test>::AlreadyVoted);
+		assert_ok!(Royalty::reject_split_change(Origin::signed(3), 0));
+		assert!(Royalty::split_proposal(0).is_some());
+		// Two of four approvals reach half of the affected payees.
+		assert_ok!(Royalty::approve_split_change(Origin::signed(5), 0));
+		assert_eq!(Royalty::distributions_of(src(b"src-1")), amended);
+		assert_eq!(Royalty::proposal_of(src(b"src-1")), None);
+	});
+}
@@ -28,7 +28,7 @@ use sp_version::NativeVersion;
 pub use sp_runtime::BuildStorage;
 pub use pallet_timestamp::Call as TimestampCall;
 pub use pallet_balances::Call as BalancesCall;
-pub use sp_runtime::{Permill, Perbill};
+pub use sp_runtime::{ModuleId, Permill, Perbill};
 pub use frame_support::{
 	construct_runtime, parameter_types, StorageValue,
 	traits::{KeyOwnerProofSystem, Randomness},
@@ -63,6 +63,12 @@ pub type AccountIndex = u32;
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Identifier of an asset of the assets pallet.
+pub type AssetId = u32;
+
+/// A timestamp: milliseconds since the unix epoch.
+pub type Moment = u64;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -237,7 +243,7 @@ parameter_types! {
 
 impl pallet_timestamp::Config for Runtime {
 	/// A timestamp: milliseconds since the unix epoch.
-	type Moment = u64;
+	type Moment = Moment;
 	type OnTimestampSet = Aura;
 	type MinimumPeriod = MinimumPeriod;
 	type WeightInfo = ();
@@ -326,8 +332,68 @@ impl pallet_template::Config for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	pub const TakedownBond: Balance = 10 * DOLLARS;
+	pub const TakedownPeriod: BlockNumber = 14 * DAYS;
+}
+
 impl pallet_rmp::Config for Runtime {
 	type Event = Event;
+	type GenreOrigin = frame_system::EnsureRoot<AccountId>;
+	type Currency = Balances;
+	type TakedownOrigin = frame_system::EnsureRoot<AccountId>;
+	type TakedownBond = TakedownBond;
+	type TakedownPeriod = TakedownPeriod;
+	type OnRightsChange = Royalty;
+	type OnTakedownSettled = Royalty;
+}
+
+parameter_types! {
+	pub const AssetDepositBase: Balance = 100 * DOLLARS;
+	pub const AssetDepositPerZombie: Balance = 1 * DOLLARS;
+	pub const StringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 10 * DOLLARS;
+	pub const MetadataDepositPerByte: Balance = 1 * DOLLARS;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDepositBase = AssetDepositBase;
+	type AssetDepositPerZombie = AssetDepositPerZombie;
+	type StringLimit = StringLimit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const RoyaltyModuleId: ModuleId = ModuleId(*b"py/royal");
+	pub const RoyaltyEscrowPeriod: BlockNumber = 90 * DAYS;
+	pub const RoyaltyStatementPeriod: BlockNumber = 30 * DAYS;
+	pub const RoyaltyStatementRetention: BlockNumber = 24;
+	pub const RoyaltyPayoutInterval: BlockNumber = DAYS;
+	pub const RoyaltyMaxPayoutWeight: Weight = WEIGHT_PER_SECOND / 10;
+	pub const SplitProposalPeriod: BlockNumber = 14 * DAYS;
+	pub const SplitApprovalThreshold: u32 = pallet_rmp::MAX_BASIS_POINTS;
+}
+
+impl pallet_royalty::Config for Runtime {
+	type Event = Event;
+	type ModuleId = RoyaltyModuleId;
+	type AssetOrigin = frame_system::EnsureRoot<AccountId>;
+	type EscrowOrigin = frame_system::EnsureRoot<AccountId>;
+	type EscrowPeriod = RoyaltyEscrowPeriod;
+	type StatementPeriod = RoyaltyStatementPeriod;
+	type StatementRetention = RoyaltyStatementRetention;
+	type WithholdingOrigin = frame_system::EnsureRoot<AccountId>;
+	type PayoutInterval = RoyaltyPayoutInterval;
+	type MaxPayoutWeight = RoyaltyMaxPayoutWeight;
+	type ProposalPeriod = SplitProposalPeriod;
+	type ApprovalThreshold = SplitApprovalThreshold;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -347,7 +413,9 @@ construct_runtime!(
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		// Include the custom logic from the template pallet in the runtime.
 		TemplateModule: pallet_template::{Module, Call, Storage, Event<T>},
+		Assets: pallet_assets::{Module, Call, Storage, Event<T>},
 		RightsMgmtPortal: pallet_rmp::{Module, Call, Storage, Event<T>},
+		Royalty: pallet_royalty::{Module, Call, Storage, Config, Event<T>},
 		Contracts: pallet_contracts::{Module, Call, Config<T>, Storage, Event<T>},
 	}
 );
@@ -412,6 +480,228 @@ impl_runtime_apis! {
             Contracts::rent_projection(address)
         }
     }
+	impl pallet_rmp_runtime_api::CatalogApi<Block, AccountId, Balance, BlockNumber, Hash, Moment> for Runtime {
+		fn music_by_src_id(src_id: pallet_rmp::SrcId) -> Option<pallet_rmp::MusicData<AccountId, Moment>> {
+			RightsMgmtPortal::music_by_src_id(src_id)
+		}
+
+		fn owner_of(src_id: pallet_rmp::SrcId) -> Option<AccountId> {
+			RightsMgmtPortal::owner_of(src_id)
+		}
+
+		fn album_by_id(album_id: pallet_rmp::AlbumId) -> Option<pallet_rmp::Album<Moment>> {
+			RightsMgmtPortal::album_by_id(album_id)
+		}
+
+		fn album_by_gtin(gtin: pallet_rmp::Gtin) -> Option<pallet_rmp::AlbumId> {
+			RightsMgmtPortal::album_by_gtin(&gtin)
+		}
+
+		fn src_localizations(src_id: pallet_rmp::SrcId) -> Option<pallet_rmp::Localizations> {
+			RightsMgmtPortal::src_localizations(src_id)
+		}
+
+		fn album_localizations(album_id: pallet_rmp::AlbumId) -> Option<pallet_rmp::Localizations> {
+			RightsMgmtPortal::album_localizations(album_id)
+		}
+
+		fn album_tracks(album_id: pallet_rmp::AlbumId) -> Vec<pallet_rmp::SrcId> {
+			RightsMgmtPortal::album_tracks(album_id)
+		}
+
+		fn territory_rights_of(src_id: pallet_rmp::SrcId) -> Vec<pallet_rmp::TerritoryRights<Moment>> {
+			RightsMgmtPortal::territory_rights_of(src_id)
+		}
+
+		fn is_available_in(src_id: pallet_rmp::SrcId, territory: pallet_rmp::Territory, time: Moment) -> bool {
+			RightsMgmtPortal::is_available_in(&src_id, &territory, time)
+		}
+
+		fn genre(genre_id: pallet_rmp::GenreId) -> Option<pallet_rmp::Genre> {
+			RightsMgmtPortal::genre(genre_id)
+		}
+
+		fn srcs_by_genre(genre_id: pallet_rmp::GenreId) -> Vec<pallet_rmp::SrcId> {
+			RightsMgmtPortal::srcs_by_genre(genre_id)
+		}
+
+		fn samples_of(src_id: pallet_rmp::SrcId) -> Vec<pallet_rmp::Sample> {
+			RightsMgmtPortal::samples_of(src_id)
+		}
+
+		fn sampled_by(src_id: pallet_rmp::SrcId) -> Vec<pallet_rmp::SrcId> {
+			RightsMgmtPortal::sampled_by(src_id)
+		}
+
+		fn has_uncleared_samples(src_id: pallet_rmp::SrcId) -> bool {
+			RightsMgmtPortal::has_uncleared_samples(&src_id)
+		}
+
+		fn composition_by_id(composition_id: pallet_rmp::CompositionId) -> Option<pallet_rmp::Comp<Moment>> {
+			RightsMgmtPortal::composition_by_id(composition_id)
+		}
+
+		fn derived_from(src_id: pallet_rmp::SrcId) -> Vec<pallet_rmp::Derivation> {
+			RightsMgmtPortal::derived_from(src_id)
+		}
+
+		fn all_derivatives(work: pallet_rmp::Work) -> Vec<pallet_rmp::SrcId> {
+			RightsMgmtPortal::all_derivatives(&work)
+		}
+
+		fn credits_of(src_id: pallet_rmp::SrcId) -> Vec<pallet_rmp::Credit<AccountId>> {
+			RightsMgmtPortal::credits_of(src_id)
+		}
+
+		fn credited_srcs(account: AccountId) -> Vec<pallet_rmp::SrcId> {
+			RightsMgmtPortal::credited_srcs(account)
+		}
+
+		fn composition_credits_of(composition_id: pallet_rmp::CompositionId) -> Vec<pallet_rmp::Credit<AccountId>> {
+			RightsMgmtPortal::composition_credits_of(composition_id)
+		}
+
+		fn party(party_id: pallet_rmp::PartyId) -> Option<pallet_rmp::Party<AccountId>> {
+			RightsMgmtPortal::party(party_id)
+		}
+
+		fn party_by_isni(isni: pallet_rmp::Isni) -> Option<pallet_rmp::PartyId> {
+			RightsMgmtPortal::party_by_isni(isni)
+		}
+
+		fn party_by_ipi(ipi: pallet_rmp::IpiNameNumber) -> Option<pallet_rmp::PartyId> {
+			RightsMgmtPortal::party_by_ipi(ipi)
+		}
+
+		fn artist(artist_id: pallet_rmp::ArtistId) -> Option<pallet_rmp::Artist<AccountId>> {
+			RightsMgmtPortal::artist(RightsMgmtPortal::canonical_artist_id(artist_id))
+		}
+
+		fn resolve_alias(name: pallet_rmp::ArtistName) -> Option<pallet_rmp::ArtistId> {
+			RightsMgmtPortal::resolve_alias(&name)
+		}
+
+		fn artists_of_src(src_id: pallet_rmp::SrcId) -> Vec<pallet_rmp::ArtistId> {
+			RightsMgmtPortal::artists_of_src(&src_id)
+		}
+
+		fn grant_chain(src_id: pallet_rmp::SrcId) -> Vec<pallet_rmp::Grant<AccountId, BlockNumber>> {
+			RightsMgmtPortal::grant_chain(src_id)
+		}
+
+		fn license_templates_of(src_id: pallet_rmp::SrcId) -> Vec<pallet_rmp::LicenseTemplate<Balance, BlockNumber>> {
+			RightsMgmtPortal::license_templates_of(src_id)
+		}
+
+		fn license(license_id: pallet_rmp::LicenseId) -> Option<pallet_rmp::License<AccountId, Balance, BlockNumber>> {
+			RightsMgmtPortal::license(license_id)
+		}
+
+		fn licenses_of(licensee: AccountId) -> Vec<pallet_rmp::LicenseId> {
+			RightsMgmtPortal::licenses_of(licensee)
+		}
+
+		fn verify_license(
+			license_id: pallet_rmp::LicenseId,
+			licensee: AccountId,
+			src_id: pallet_rmp::SrcId,
+			license_type: pallet_rmp::LicenseType,
+			territory: pallet_rmp::Territory,
+		) -> bool {
+			RightsMgmtPortal::verify_license(license_id, &licensee, &src_id, license_type, &territory)
+		}
+
+		fn takedown_of(src_id: pallet_rmp::SrcId) -> Option<pallet_rmp::Takedown<AccountId, Balance, BlockNumber, Hash>> {
+			RightsMgmtPortal::takedown_of(src_id)
+		}
+
+		fn is_suspended(src_id: pallet_rmp::SrcId) -> bool {
+			RightsMgmtPortal::is_suspended(src_id)
+		}
+	}
+
+	impl pallet_royalty_runtime_api::RoyaltyApi<Block, AccountId, Balance, AssetId, Balance, BlockNumber> for Runtime {
+		fn ledger_of(src_id: pallet_royalty::SrcId) -> pallet_royalty::SrcLedger<Balance> {
+			Royalty::ledger_of(src_id)
+		}
+
+		fn accrued_of(payee: AccountId) -> Vec<(pallet_royalty::SrcId, Balance)> {
+			Royalty::accrued_of(&payee)
+		}
+
+		fn asset_ledger_of(src_id: pallet_royalty::SrcId, asset_id: AssetId) -> pallet_royalty::SrcLedger<Balance> {
+			Royalty::asset_ledger_of(src_id, asset_id)
+		}
+
+		fn accrued_assets_of(payee: AccountId) -> Vec<(pallet_royalty::SrcId, AssetId, Balance)> {
+			Royalty::accrued_assets_of(&payee)
+		}
+
+		fn recoupment(recoupment_id: pallet_royalty::RecoupmentId) -> Option<pallet_royalty::Recoupment<AccountId, Balance>> {
+			Royalty::recoupment(recoupment_id)
+		}
+
+		fn recoupments_of(
+			src_id: pallet_royalty::SrcId,
+		) -> Vec<(pallet_royalty::RecoupmentId, pallet_royalty::Recoupment<AccountId, Balance>)> {
+			Royalty::recoupments_of(src_id)
+				.into_iter()
+				.filter_map(|id| Royalty::recoupment(id).map(|recoupment| (id, recoupment)))
+				.collect()
+		}
+
+		fn escrow_of(src_id: pallet_royalty::SrcId) -> Option<pallet_royalty::Escrow<AccountId, Balance, BlockNumber>> {
+			Royalty::escrow_of(src_id)
+		}
+
+		fn asset_escrow_of(
+			src_id: pallet_royalty::SrcId,
+			asset_id: AssetId,
+		) -> Option<pallet_royalty::Escrow<AccountId, Balance, BlockNumber>> {
+			Royalty::asset_escrow_of(src_id, asset_id)
+		}
+
+		fn current_period() -> BlockNumber {
+			Royalty::current_period()
+		}
+
+		fn statement(payee: AccountId, period: BlockNumber) -> pallet_royalty::Statement<Balance, AssetId, Balance, BlockNumber> {
+			Royalty::statement(&payee, period)
+		}
+
+		fn split_group(split_group_id: pallet_royalty::SplitGroupId) -> Option<pallet_royalty::SplitGroup<AccountId>> {
+			Royalty::split_group(split_group_id)
+		}
+
+		fn tax_account() -> Option<AccountId> {
+			Royalty::tax_account()
+		}
+
+		fn withholding_rate_of(payee: AccountId) -> u32 {
+			Royalty::withholding_rate_of(payee)
+		}
+
+		fn payout_threshold_of(payee: AccountId) -> Balance {
+			Royalty::payout_threshold_of(payee)
+		}
+
+		fn accrued_total_of(payee: AccountId) -> Balance {
+			Royalty::accrued_total_of(payee)
+		}
+
+		fn split_proposal(proposal_id: pallet_royalty::ProposalId) -> Option<pallet_royalty::SplitProposal<AccountId, BlockNumber>> {
+			Royalty::split_proposal(proposal_id)
+		}
+
+		fn proposal_of(src_id: pallet_royalty::SrcId) -> Option<pallet_royalty::ProposalId> {
+			Royalty::proposal_of(src_id)
+		}
+
+		fn split_history(src_id: pallet_royalty::SrcId) -> Vec<pallet_royalty::SplitRevision<AccountId, BlockNumber>> {
+			Royalty::split_history(src_id)
+		}
+	}
+
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
 			VERSION